@@ -0,0 +1,52 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! The first byte of the input selects the route, the rest is the input data.
+
+#![no_main]
+
+use checkpwn_lib::fuzzing::{arg_to_api_route, CheckableChoices};
+use checkpwn_lib::{HIBP_BASE_URL, HIBP_PASS_BASE_URL};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let (choice, input_data) = match data.split_first() {
+        Some((choice, rest)) => match std::str::from_utf8(rest) {
+            Ok(input_data) if !input_data.is_empty() => (*choice, input_data),
+            _ => return,
+        },
+        None => return,
+    };
+    let (arg, base_url) = match choice % 4 {
+        0 => (CheckableChoices::Acc, HIBP_BASE_URL),
+        1 => (CheckableChoices::Paste, HIBP_BASE_URL),
+        2 => (CheckableChoices::Pass, HIBP_PASS_BASE_URL),
+        _ => (CheckableChoices::PassNtlm, HIBP_PASS_BASE_URL),
+    };
+
+    if let Ok(url) = arg_to_api_route(&arg, input_data, base_url) {
+        assert!(url.starts_with(base_url));
+        // Everything that isn't URL-safe must have been percent-encoded
+        assert!(url.chars().all(|c| c.is_ascii_graphic()));
+        assert!(!url[base_url.len()..].contains('#'));
+    }
+});
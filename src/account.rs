@@ -0,0 +1,66 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::CheckpwnError;
+use zeroize::Zeroizing;
+
+/// `Account` is a wrapper type for an account, e.g. an email address or username, that is checked
+/// at HIBP. The account is zeroized when dropped, and it contains an opaque `Debug` impl to avoid
+/// leaking it.
+#[derive(Clone)]
+pub struct Account(Zeroizing<String>);
+
+impl Account {
+    /// Make a new `Account`. Returns `Err` if `account` is empty.
+    pub fn new(account: &str) -> Result<Self, CheckpwnError> {
+        if account.is_empty() {
+            return Err(CheckpwnError::EmptyInput);
+        }
+
+        Ok(Self(Zeroizing::new(account.to_string())))
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for Account {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Account(***REDACTED***)")
+    }
+}
+
+#[test]
+fn test_account_new() {
+    assert_eq!(Account::new("").unwrap_err(), CheckpwnError::EmptyInput);
+    assert_eq!(
+        Account::new("test@example.com").unwrap().as_str(),
+        "test@example.com"
+    );
+}
+
+#[test]
+fn test_account_debug() {
+    let account = Account::new("test@example.com").unwrap();
+    assert_eq!(format!("{:?}", account), "Account(***REDACTED***)");
+}
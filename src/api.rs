@@ -21,105 +21,325 @@
 // SOFTWARE.
 
 use crate::errors::CheckpwnError;
+use crate::AccountBreachResult;
+use md4::Md4;
+use percent_encoding::{utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
 use sha1::{Digest, Sha1};
+use std::time::Duration;
+use subtle::ConstantTimeEq;
 
+/// Characters that are percent-encoded in accounts: everything except the unreserved
+/// characters of RFC 3986.
+const ACCOUNT_ENCODE_SET: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'.')
+    .remove(b'-')
+    .remove(b'_')
+    .remove(b'~');
+
+/// Number of hash characters sent to the HIBP range API, i.e. the length of a range prefix.
+pub const RANGE_PREFIX_LEN: usize = 5;
+
+/// Base URL of the HIBP API, used for account, paste and breach lookups.
+pub const HIBP_BASE_URL: &str = "https://haveibeenpwned.com/api/v3";
+
+/// Base URL of the Pwned Passwords API, used for password range lookups.
+pub const HIBP_PASS_BASE_URL: &str = "https://api.pwnedpasswords.com";
+
+/// The HIBP API routes that `arg_to_api_route` makes URLs for.
 pub enum CheckableChoices {
+    /// Breached account lookup.
     Acc,
+    /// SHA1 password range lookup.
     Pass,
+    /// NTLM password range lookup.
+    PassNtlm,
+    /// Paste account lookup.
     Paste,
+    // Only used by endpoints that decode JSON responses
+    /// Single breach lookup, by name.
+    #[cfg(feature = "serde")]
+    Breach,
+    /// All breaches.
+    #[cfg(feature = "serde")]
+    AllBreaches,
+    /// All data classes.
+    #[cfg(feature = "serde")]
+    DataClasses,
+    /// The most recently added breach.
+    #[cfg(feature = "serde")]
+    LatestBreach,
+    /// Subscription status of the API key.
+    #[cfg(feature = "serde")]
+    SubscriptionStatus,
 }
 
 impl CheckableChoices {
-    fn get_api_route(&self, search_term: &str) -> String {
+    /// Whether this route is served by the Pwned Passwords API, rather than the HIBP API.
+    pub fn is_password(&self) -> bool {
+        matches!(self, CheckableChoices::Pass | CheckableChoices::PassNtlm)
+    }
+
+    fn get_api_route(&self, base_url: &str, search_term: &str) -> String {
+        let base_url = base_url.trim_end_matches('/');
         match self {
-            CheckableChoices::Acc => format!(
-                "https://haveibeenpwned.com/api/v3/breachedaccount/{}",
-                search_term
-            ),
-            CheckableChoices::Pass => {
-                format!("https://api.pwnedpasswords.com/range/{}", search_term)
-            }
-            CheckableChoices::Paste => format!(
-                "https://haveibeenpwned.com/api/v3/pasteaccount/{}",
-                search_term
-            ),
+            CheckableChoices::Acc => format!("{}/breachedaccount/{}", base_url, search_term),
+            CheckableChoices::Pass => format!("{}/range/{}", base_url, search_term),
+            CheckableChoices::PassNtlm => format!("{}/range/{}?mode=ntlm", base_url, search_term),
+            CheckableChoices::Paste => format!("{}/pasteaccount/{}", base_url, search_term),
+            #[cfg(feature = "serde")]
+            CheckableChoices::Breach => format!("{}/breach/{}", base_url, search_term),
+            // These take no search term
+            #[cfg(feature = "serde")]
+            CheckableChoices::AllBreaches => format!("{}/breaches", base_url),
+            #[cfg(feature = "serde")]
+            CheckableChoices::DataClasses => format!("{}/dataclasses", base_url),
+            #[cfg(feature = "serde")]
+            CheckableChoices::LatestBreach => format!("{}/latestbreach", base_url),
+            #[cfg(feature = "serde")]
+            CheckableChoices::SubscriptionStatus => format!("{}/subscription/status", base_url),
         }
     }
 }
 
 /// Take the user-supplied command-line arguments and make a URL for the HIBP API.
-/// If the `pass` argument has been selected, `input_data` needs to be the hashed password.
-pub fn arg_to_api_route(arg: &CheckableChoices, input_data: &str) -> String {
+/// If a `pass` argument has been selected, `input_data` needs to be the hashed password.
+/// Accounts are percent-encoded, so that e.g. `+` in email aliases is sent correctly.
+/// The URL is made relative to `base_url`, which in production is `HIBP_PASS_BASE_URL` for a
+/// `pass` argument and `HIBP_BASE_URL` otherwise.
+/// Returns `Err(CheckpwnError::InvalidHash)` if `input_data` does not start with a valid hash prefix
+/// for a `pass` argument.
+pub fn arg_to_api_route(
+    arg: &CheckableChoices,
+    input_data: &str,
+    base_url: &str,
+) -> Result<String, CheckpwnError> {
     match arg {
-        CheckableChoices::Pass => arg.get_api_route(
+        CheckableChoices::Pass | CheckableChoices::PassNtlm => {
+            if input_data.is_empty() {
+                return Err(CheckpwnError::EmptyInput);
+            }
             // Only send the first 5 chars to the password range API
-            &input_data[..5],
-        ),
-        _ => arg.get_api_route(input_data),
+            let prefix = input_data.get(..RANGE_PREFIX_LEN).unwrap_or(input_data);
+            if !validate_sha1_prefix(prefix) {
+                return Err(CheckpwnError::InvalidHash);
+            }
+
+            Ok(arg.get_api_route(base_url, prefix))
+        }
+        _ => Ok(arg.get_api_route(base_url, &encode_account(input_data))),
     }
 }
 
+/// Percent-encode `account` for use in a HIBP API URL.
+pub fn encode_account(account: &str) -> String {
+    utf8_percent_encode(account, ACCOUNT_ENCODE_SET).to_string()
+}
+
+/// Check that `prefix` is a valid range API prefix, i.e. 5 hexadecimal characters.
+pub fn validate_sha1_prefix(prefix: &str) -> bool {
+    prefix.len() == RANGE_PREFIX_LEN && prefix.chars().all(|c| c.is_ascii_hexdigit())
+}
+
 /// Find matching key in received set of keys.
-pub fn search_in_range(password_range_response: &str, hashed_key: &str) -> bool {
+/// Returns the number of times the key has been seen in breaches, or `None` if it's not in the range.
+/// `Err(CheckpwnError::MalformedResponse)` is returned if a line is not a `HASH:COUNT` pair.
+pub fn search_in_range(
+    password_range_response: &str,
+    hashed_key: &str,
+) -> Result<Option<u64>, CheckpwnError> {
+    // Don't include first five characters of own password, as this also is how
+    // the HIBP API returns passwords.
+    let hashed_suffix = hashed_key
+        .as_bytes()
+        .get(5..)
+        .ok_or(CheckpwnError::InvalidHash)?;
+
     for line in password_range_response.lines() {
-        let pair: Vec<_> = line.split(':').collect();
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let (hash, count) = line
+            .split_once(':')
+            .ok_or(CheckpwnError::MalformedResponse)?;
+        let count: u64 = count.trim().parse()?;
         // Padded entries always have an occurrence of 0 and should be
         // discarded.
-        if *pair.get(1).unwrap() == "0" {
+        if count == 0 {
             continue;
         }
 
         // Each response is truncated to only be the hash, no whitespace, etc.
-        // All hashes here have a length of 35, so the useless gets dropped by
-        // slicing. Don't include first five characters of own password, as
-        // this also is how the HIBP API returns passwords.
-        if *pair.get(0).unwrap() == &hashed_key[5..] {
-            return true;
+        // Compared in constant time to not leak how much of the hash matches
+        // each entry.
+        if bool::from(hash.as_bytes().ct_eq(hashed_suffix)) {
+            return Ok(Some(count));
         }
     }
 
-    false
+    Ok(None)
 }
 
 /// Match a Responses errors to codes and results that checkpwn can use.
+/// Returns the status code together with the response.
 pub fn response_to_status_codes(
-    response: &Result<ureq::Response, ureq::Error>,
-) -> Result<u16, CheckpwnError> {
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<(u16, ureq::Response), CheckpwnError> {
     match response {
-        Ok(resp) => Ok(resp.status()),
-        Err(ureq::Error::Status(code, _)) => Ok(*code),
-        Err(_) => Err(CheckpwnError::Network),
+        Ok(resp) => Ok((resp.status(), resp)),
+        // Status codes are evaluated by the caller, except for rate limiting.
+        Err(ureq::Error::Status(code, resp)) if code != 429 => Ok((code, resp)),
+        Err(err) => Err(err.into()),
     }
 }
 
+/// Check whether a transport error was caused by an IO timeout.
+pub fn is_timeout(transport: &ureq::Transport) -> bool {
+    use std::error::Error;
+
+    transport
+        .source()
+        .and_then(|source| source.downcast_ref::<std::io::Error>())
+        .is_some_and(|io_err| {
+            matches!(
+                io_err.kind(),
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+            )
+        })
+}
+
+/// Parse the value of a `Retry-After` header, given in seconds by HIBP.
+/// Falls back to `DEFAULT_RATE_LIMIT_DELAY` if the header is missing or invalid.
+pub fn parse_retry_after(value: Option<&str>) -> Duration {
+    value
+        .and_then(|secs| secs.trim().parse().ok())
+        .map(Duration::from_secs)
+        .unwrap_or(crate::DEFAULT_RATE_LIMIT_DELAY)
+}
+
 pub fn evaluate_acc_breach_statuscodes(
     acc_stat: u16,
     paste_stat: u16,
 ) -> Result<bool, CheckpwnError> {
+    evaluate_acc_breach_statuscodes_detailed(acc_stat, paste_stat).map(|res| res.any_breached())
+}
+
+/// Like `evaluate_acc_breach_statuscodes`, but keeps the result for each database separate.
+pub fn evaluate_acc_breach_statuscodes_detailed(
+    acc_stat: u16,
+    paste_stat: u16,
+) -> Result<AccountBreachResult, CheckpwnError> {
     match (acc_stat, paste_stat) {
-        (401, 401) => Err(CheckpwnError::InvalidApiKey),
-        (404, 404) => Ok(false),
+        // Both requests use the same API key, so it's invalid if either was rejected.
+        (401, _) | (_, 401) => Err(CheckpwnError::InvalidApiKey),
+        (500 | 502 | 503, _) | (_, 500 | 502 | 503) => Err(CheckpwnError::ServiceUnavailable),
+        (404, 404) => Ok(AccountBreachResult {
+            account_breached: false,
+            paste_breached: false,
+        }),
         // BadRequest allowed here because the account API lets you search for usernames
         // and the paste API will return BadRequest on those
-        (404, 400) => Ok(false),
+        (404, 400) => Ok(AccountBreachResult {
+            account_breached: false,
+            paste_breached: false,
+        }),
         (400, 400) => Err(CheckpwnError::BadResponse),
         // Since the account API both takes username and emails and situation where BadRequest
         // and NotFound are returned should never occur.
         (400, 404) => Err(CheckpwnError::BadResponse),
         (400, 200) => Err(CheckpwnError::BadResponse),
-        _ => Ok(true),
+        (200 | 404, 200 | 404 | 400) => Ok(AccountBreachResult {
+            account_breached: acc_stat == 200,
+            paste_breached: paste_stat == 200,
+        }),
+        (200 | 404 | 400, code) | (code, _) => Err(CheckpwnError::UnexpectedStatus(code)),
+    }
+}
+
+/// Make the URL of the HIBP range API for a SHA1 hash `prefix`, e.g.
+/// `https://api.pwnedpasswords.com/range/B1B37` for `B1B37`.
+/// Returns `Err(CheckpwnError::EmptyInput)` if `prefix` is empty, and
+/// `Err(CheckpwnError::InvalidHash)` if it is not exactly `RANGE_PREFIX_LEN` hexadecimal characters.
+pub fn password_prefix_to_range_url(prefix: &str) -> Result<String, CheckpwnError> {
+    if prefix.is_empty() {
+        return Err(CheckpwnError::EmptyInput);
+    }
+    if !validate_sha1_prefix(prefix) {
+        return Err(CheckpwnError::InvalidHash);
     }
+
+    Ok(CheckableChoices::Pass.get_api_route(HIBP_PASS_BASE_URL, prefix))
 }
 
-/// Return SHA1 digest of string.
+/// Return the SHA1 digest of `password` as uppercase hex, the way the HIBP range API lists it.
+///
+/// SHA1 is not suitable for storing passwords. This exists only to make the hashes for HIBP
+/// k-anonymity queries, of which the first `RANGE_PREFIX_LEN` characters are sent to HIBP.
 pub fn hash_password(password: &str) -> String {
+    hash_password_bytes(password.as_bytes())
+}
+
+/// Return SHA1 digest of raw bytes.
+pub fn hash_password_bytes(password: &[u8]) -> String {
     let mut sha_digest = Sha1::default();
-    sha_digest.update(password.as_bytes());
+    sha_digest.update(password);
     // Make uppercase for easier comparison with
     // HIBP API response
     hex::encode(sha_digest.finalize()).to_uppercase()
 }
 
+/// Return NTLM digest of string, i.e. the MD4 digest of its UTF-16LE encoding.
+pub fn hash_password_ntlm(password: &str) -> String {
+    let mut md4_digest = Md4::default();
+    for unit in password.encode_utf16() {
+        md4_digest.update(unit.to_le_bytes());
+    }
+    // Make uppercase for easier comparison with
+    // HIBP API response
+    hex::encode(md4_digest.finalize()).to_uppercase()
+}
+
+/// Check that `hex` is a full SHA1 digest, i.e. 40 hexadecimal characters.
+pub fn validate_sha1_hex(hex: &str) -> bool {
+    hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Check that `account` looks like an email address: a non-empty local part and a
+/// domain with at least two non-empty labels, separated by a single `@`.
+pub fn validate_account_string(account: &str) -> bool {
+    if account.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return false;
+    }
+
+    let mut parts = account.split('@');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(local), Some(domain), None) => {
+            !local.is_empty()
+                && domain.contains('.')
+                && domain.split('.').all(|label| !label.is_empty())
+        }
+        _ => false,
+    }
+}
+
+#[test]
+fn test_password_prefix_to_range_url() {
+    assert_eq!(
+        password_prefix_to_range_url("B1B37").unwrap(),
+        "https://api.pwnedpasswords.com/range/B1B37"
+    );
+    assert_eq!(
+        password_prefix_to_range_url("").unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    for invalid in ["B1B3", "B1B377", "G1B37"].iter() {
+        assert_eq!(
+            password_prefix_to_range_url(invalid).unwrap_err(),
+            CheckpwnError::InvalidHash
+        );
+    }
+}
+
 #[test]
 fn test_sha1() {
     let hash = hash_password("qwerty");
@@ -129,21 +349,140 @@ fn test_sha1() {
     );
 }
 
+#[test]
+fn test_ntlm() {
+    assert_eq!(
+        hash_password_ntlm("password"),
+        "8846F7EAEE8FB117AD06BDD830B7586C"
+    );
+    assert_eq!(
+        hash_password_ntlm("Password1"),
+        "64F12CDDAA88057E06A81B54E73B949B"
+    );
+    assert_eq!(hash_password_ntlm(""), "31D6CFE0D16AE931B73C59D7E0C089C0");
+}
+
+#[test]
+fn test_validate_sha1_hex() {
+    assert!(validate_sha1_hex(&hash_password("qwerty")));
+    assert!(validate_sha1_hex(
+        "b1b3773a05c0ed0176787a4f1574ff0075f7521e"
+    ));
+    assert!(!validate_sha1_hex(""));
+    // 39 and 41 characters
+    assert!(!validate_sha1_hex(
+        "B1B3773A05C0ED0176787A4F1574FF0075F7521"
+    ));
+    assert!(!validate_sha1_hex(
+        "B1B3773A05C0ED0176787A4F1574FF0075F7521EE"
+    ));
+    // Non-hex character
+    assert!(!validate_sha1_hex(
+        "G1B3773A05C0ED0176787A4F1574FF0075F7521E"
+    ));
+}
+
+#[test]
+fn test_validate_account_string() {
+    assert!(validate_account_string("test@example.com"));
+    assert!(validate_account_string("user+tag@mail.example.co.uk"));
+
+    assert!(!validate_account_string(""));
+    assert!(!validate_account_string("username"));
+    assert!(!validate_account_string("@example.com"));
+    assert!(!validate_account_string("test@"));
+    assert!(!validate_account_string("test@example"));
+    assert!(!validate_account_string("test@example..com"));
+    assert!(!validate_account_string("test@.example.com"));
+    assert!(!validate_account_string("test@@example.com"));
+    assert!(!validate_account_string("te st@example.com"));
+    assert!(!validate_account_string("test@example.com\n"));
+}
+
 #[test]
 fn test_make_req_and_arg_to_route() {
     // API paths taken from https://haveibeenpwned.com/API/v3
-    let path = CheckableChoices::Acc.get_api_route("test@example.com");
+    let path = CheckableChoices::Acc.get_api_route(HIBP_BASE_URL, "test@example.com");
     assert_eq!(
         path,
         "https://haveibeenpwned.com/api/v3/breachedaccount/test@example.com"
     );
     assert_eq!(
         "https://api.pwnedpasswords.com/range/B1B37",
-        arg_to_api_route(&CheckableChoices::Pass, &hash_password("qwerty"))
+        arg_to_api_route(
+            &CheckableChoices::Pass,
+            &hash_password("qwerty"),
+            HIBP_PASS_BASE_URL
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        "https://api.pwnedpasswords.com/range/8846F?mode=ntlm",
+        arg_to_api_route(
+            &CheckableChoices::PassNtlm,
+            &hash_password_ntlm("password"),
+            HIBP_PASS_BASE_URL
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/pasteaccount/test%40example.com",
+        arg_to_api_route(&CheckableChoices::Paste, "test@example.com", HIBP_BASE_URL).unwrap()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_info_routes() {
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/breach/Adobe",
+        arg_to_api_route(&CheckableChoices::Breach, "Adobe", HIBP_BASE_URL).unwrap()
+    );
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/breach/Not%20A%2FBreach",
+        arg_to_api_route(&CheckableChoices::Breach, "Not A/Breach", HIBP_BASE_URL).unwrap()
+    );
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/breaches",
+        arg_to_api_route(&CheckableChoices::AllBreaches, "", HIBP_BASE_URL).unwrap()
+    );
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/dataclasses",
+        arg_to_api_route(&CheckableChoices::DataClasses, "", HIBP_BASE_URL).unwrap()
+    );
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/latestbreach",
+        arg_to_api_route(&CheckableChoices::LatestBreach, "", HIBP_BASE_URL).unwrap()
+    );
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/subscription/status",
+        arg_to_api_route(&CheckableChoices::SubscriptionStatus, "", HIBP_BASE_URL).unwrap()
+    );
+}
+
+#[test]
+fn test_arg_to_route_encodes_accounts() {
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/breachedaccount/user%2Btag%40example.com",
+        arg_to_api_route(
+            &CheckableChoices::Acc,
+            "user+tag@example.com",
+            HIBP_BASE_URL
+        )
+        .unwrap()
+    );
+    assert_eq!(
+        "https://haveibeenpwned.com/api/v3/pasteaccount/100%25_real-user~1%40example.com",
+        arg_to_api_route(
+            &CheckableChoices::Paste,
+            "100%_real-user~1@example.com",
+            HIBP_BASE_URL
+        )
+        .unwrap()
     );
     assert_eq!(
-        "https://haveibeenpwned.com/api/v3/pasteaccount/test@example.com",
-        arg_to_api_route(&CheckableChoices::Paste, "test@example.com")
+        "https://haveibeenpwned.com/api/v3/breachedaccount/some%2Fuser%3Fx",
+        arg_to_api_route(&CheckableChoices::Acc, "some/user?x", HIBP_BASE_URL).unwrap()
     );
 }
 
@@ -152,7 +491,59 @@ fn test_good_argument() {
     let option_arg = CheckableChoices::Acc;
     let data_search = String::from("test@example.com");
 
-    arg_to_api_route(&option_arg, &data_search);
+    arg_to_api_route(&option_arg, &data_search, HIBP_BASE_URL).unwrap();
+}
+
+#[test]
+fn test_validate_sha1_prefix() {
+    assert!(validate_sha1_prefix("B1B37"));
+    assert!(validate_sha1_prefix("b1b37"));
+    assert!(!validate_sha1_prefix(""));
+    assert!(!validate_sha1_prefix("B1B3"));
+    assert!(!validate_sha1_prefix("B1B377"));
+    assert!(!validate_sha1_prefix("B1B3Z"));
+}
+
+#[test]
+fn test_arg_to_api_route_base_url() {
+    assert_eq!(
+        arg_to_api_route(
+            &CheckableChoices::Acc,
+            "test@example.com",
+            "http://127.0.0.1:8080/"
+        )
+        .unwrap(),
+        "http://127.0.0.1:8080/breachedaccount/test%40example.com"
+    );
+    assert_eq!(
+        arg_to_api_route(&CheckableChoices::Pass, "B1B37", "http://127.0.0.1:8080").unwrap(),
+        "http://127.0.0.1:8080/range/B1B37"
+    );
+}
+
+#[test]
+fn test_arg_to_api_route_bad_prefix() {
+    assert_eq!(
+        arg_to_api_route(&CheckableChoices::Pass, "B1B3", HIBP_PASS_BASE_URL).unwrap_err(),
+        CheckpwnError::InvalidHash
+    );
+    assert_eq!(
+        arg_to_api_route(&CheckableChoices::Pass, "", HIBP_PASS_BASE_URL).unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    assert_eq!(
+        arg_to_api_route(&CheckableChoices::Pass, "XYZ12345", HIBP_PASS_BASE_URL).unwrap_err(),
+        CheckpwnError::InvalidHash
+    );
+    assert_eq!(
+        arg_to_api_route(&CheckableChoices::PassNtlm, "8846", HIBP_PASS_BASE_URL).unwrap_err(),
+        CheckpwnError::InvalidHash
+    );
+    // Multi-byte characters must not cause a panic when slicing
+    assert_eq!(
+        arg_to_api_route(&CheckableChoices::Pass, "ééééé", HIBP_PASS_BASE_URL).unwrap_err(),
+        CheckpwnError::InvalidHash
+    );
 }
 #[test]
 fn test_search_success_and_failure() {
@@ -188,8 +579,88 @@ fn test_search_success_and_failure() {
 
     let hashed_password = hash_password("qwerty");
 
-    assert!(search_in_range(&contains_pass, &hashed_password));
-    assert!(!search_in_range(&no_pass, &hashed_password));
+    assert_eq!(
+        search_in_range(&contains_pass, &hashed_password),
+        Ok(Some(3752262))
+    );
+    assert_eq!(search_in_range(&no_pass, &hashed_password), Ok(None));
+}
+
+#[test]
+fn test_search_near_matches() {
+    let hashed_password = hash_password("qwerty");
+
+    // Differs only in the last character
+    assert_eq!(
+        search_in_range("73A05C0ED0176787A4F1574FF0075F7521F:5", &hashed_password),
+        Ok(None)
+    );
+    // Truncated entry
+    assert_eq!(
+        search_in_range("73A05C0ED0176787A4F1574FF0075F752:5", &hashed_password),
+        Ok(None)
+    );
+    assert_eq!(
+        search_in_range(
+            "73A05C0ED0176787A4F1574FF0075F7521F:5\n73A05C0ED0176787A4F1574FF0075F7521E:7",
+            &hashed_password
+        ),
+        Ok(Some(7))
+    );
+}
+
+#[test]
+fn test_search_malformed_range() {
+    let hashed_password = hash_password("qwerty");
+
+    for malformed in [
+        "73A05C0ED0176787A4F1574FF0075F7521E",
+        "73A05C0ED0176787A4F1574FF0075F7521E;5",
+        "73A05C0ED0176787A4F1574FF0075F7521E:",
+        "73A05C0ED0176787A4F1574FF0075F7521E:five",
+        "73A05C0ED0176787A4F1574FF0075F7521E:-5",
+        "73A05C0ED0176787A4F1574FF0075F7521E:5:5",
+        "73A05C0ED0176787A4F1574FF0075F7521E:99999999999999999999999",
+        "7FF32ECF384A7DBD7F1325F2AA9421747D8:0\n<html>",
+        "\u{0}:\u{0}",
+    ]
+    .iter()
+    {
+        assert_eq!(
+            search_in_range(malformed, &hashed_password),
+            Err(CheckpwnError::MalformedResponse),
+            "{:?}",
+            malformed
+        );
+    }
+
+    // Random lines never panic
+    use rand::{Rng, RngCore};
+    let mut rng = rand::thread_rng();
+    for _ in 0..1000 {
+        let len = rng.gen_range(0..64);
+        let line: String = (0..len)
+            .map(|_| match rng.next_u32() % 4 {
+                0 => ':',
+                1 => '\n',
+                _ => rng.gen_range('0'..='F'),
+            })
+            .collect();
+        let _ = search_in_range(&line, &hashed_password);
+    }
+
+    // Blank lines are skipped
+    assert_eq!(
+        search_in_range(
+            "\r\n73A05C0ED0176787A4F1574FF0075F7521E:5\r\n",
+            &hashed_password
+        ),
+        Ok(Some(5))
+    );
+    assert_eq!(
+        search_in_range("73A05C0ED0176787A4F1574FF0075F7521E:5", "B1B3"),
+        Err(CheckpwnError::InvalidHash)
+    );
 }
 
 #[test]
@@ -212,3 +683,130 @@ fn test_evaluate_breach_good() {
     assert!(evaluate_acc_breach_statuscodes(400, 404).is_err()); // _badrequest_notfound
     assert!(evaluate_acc_breach_statuscodes(400, 200).is_err()); // _badrequest_ok
 }
+
+#[test]
+fn test_evaluate_breach_detailed() {
+    let detailed = |acc, paste| evaluate_acc_breach_statuscodes_detailed(acc, paste).unwrap();
+
+    assert!(detailed(200, 200).both_breached());
+    assert!(detailed(200, 404).account_breached);
+    assert!(!detailed(200, 404).paste_breached);
+    assert!(!detailed(404, 200).account_breached);
+    assert!(detailed(404, 200).paste_breached);
+    assert!(detailed(200, 400).account_breached);
+    assert!(!detailed(200, 400).paste_breached);
+    assert!(!detailed(404, 400).any_breached());
+    assert!(!detailed(404, 404).any_breached());
+
+    assert!(evaluate_acc_breach_statuscodes_detailed(401, 401).is_err());
+    assert!(evaluate_acc_breach_statuscodes_detailed(400, 400).is_err());
+    assert!(evaluate_acc_breach_statuscodes_detailed(400, 404).is_err());
+    assert!(evaluate_acc_breach_statuscodes_detailed(400, 200).is_err());
+}
+
+#[test]
+fn test_evaluate_acc_breach_statuscodes_unauthorized() {
+    for other in [200, 400, 401, 404, 429, 500, 503].iter() {
+        assert_eq!(
+            evaluate_acc_breach_statuscodes(401, *other),
+            Err(CheckpwnError::InvalidApiKey)
+        );
+        assert_eq!(
+            evaluate_acc_breach_statuscodes(*other, 401),
+            Err(CheckpwnError::InvalidApiKey)
+        );
+        assert_eq!(
+            evaluate_acc_breach_statuscodes_detailed(401, *other),
+            Err(CheckpwnError::InvalidApiKey)
+        );
+        assert_eq!(
+            evaluate_acc_breach_statuscodes_detailed(*other, 401),
+            Err(CheckpwnError::InvalidApiKey)
+        );
+    }
+}
+
+#[test]
+fn test_evaluate_acc_breach_statuscodes_unavailable() {
+    for (acc, paste) in [
+        (503, 200),
+        (503, 404),
+        (200, 503),
+        (404, 503),
+        (503, 503),
+        (500, 404),
+        (200, 500),
+        (502, 200),
+        (404, 502),
+    ]
+    .iter()
+    {
+        assert_eq!(
+            evaluate_acc_breach_statuscodes(*acc, *paste),
+            Err(CheckpwnError::ServiceUnavailable)
+        );
+    }
+}
+
+#[test]
+fn test_evaluate_acc_breach_statuscodes_unexpected() {
+    assert_eq!(
+        evaluate_acc_breach_statuscodes(418, 200),
+        Err(CheckpwnError::UnexpectedStatus(418))
+    );
+    assert_eq!(
+        evaluate_acc_breach_statuscodes(200, 418),
+        Err(CheckpwnError::UnexpectedStatus(418))
+    );
+    assert_eq!(
+        evaluate_acc_breach_statuscodes(404, 204),
+        Err(CheckpwnError::UnexpectedStatus(204))
+    );
+    assert_eq!(
+        evaluate_acc_breach_statuscodes(403, 429),
+        Err(CheckpwnError::UnexpectedStatus(403))
+    );
+}
+
+#[test]
+fn test_parse_retry_after() {
+    assert_eq!(parse_retry_after(Some("2")), Duration::from_secs(2));
+    assert_eq!(parse_retry_after(Some(" 10 ")), Duration::from_secs(10));
+    assert_eq!(parse_retry_after(None), crate::DEFAULT_RATE_LIMIT_DELAY);
+    assert_eq!(
+        parse_retry_after(Some("Wed, 21 Oct 2015 07:28:00 GMT")),
+        crate::DEFAULT_RATE_LIMIT_DELAY
+    );
+}
+
+#[test]
+fn test_response_to_status_codes() {
+    let parse = |raw: &str| -> ureq::Response { raw.parse().unwrap() };
+    let status = |response| response_to_status_codes(response).map(|(status, _)| status);
+
+    assert_eq!(status(Ok(parse("HTTP/1.1 200 OK\r\n\r\n"))), Ok(200));
+    assert_eq!(
+        status(Err(ureq::Error::Status(
+            404,
+            parse("HTTP/1.1 404 Not Found\r\n\r\n")
+        ))),
+        Ok(404)
+    );
+    assert_eq!(
+        status(Err(ureq::Error::Status(
+            429,
+            parse("HTTP/1.1 429 Too Many Requests\r\nRetry-After: 3\r\n\r\n")
+        ))),
+        Err(CheckpwnError::RateLimited(Duration::from_secs(3)))
+    );
+
+    let io_err = |kind| ureq::Error::from(std::io::Error::new(kind, "test"));
+    assert_eq!(
+        status(Err(io_err(std::io::ErrorKind::TimedOut))),
+        Err(CheckpwnError::Timeout)
+    );
+    assert!(matches!(
+        status(Err(io_err(std::io::ErrorKind::ConnectionRefused))),
+        Err(CheckpwnError::NetworkError(_))
+    ));
+}
@@ -0,0 +1,140 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::CheckpwnError;
+use zeroize::Zeroizing;
+
+/// `ApiKey` is a wrapper type for a HIBP API key.
+/// The key is zeroized when dropped, and its `Debug` impl only shows `ApiKey::masked`.
+#[derive(Clone)]
+pub struct ApiKey(Zeroizing<String>);
+
+impl ApiKey {
+    /// Make a new `ApiKey`. Returns `Err` if `key` is empty.
+    pub fn new(key: &str) -> Result<Self, CheckpwnError> {
+        if key.is_empty() {
+            return Err(CheckpwnError::EmptyInput);
+        }
+
+        Ok(Self(Zeroizing::new(key.to_string())))
+    }
+
+    /// Make a new `ApiKey` from the value of the environment variable `var`.
+    /// Returns `Err(CheckpwnError::MissingApiKey)` if the variable is not set or not valid
+    /// unicode, and `Err(CheckpwnError::EmptyInput)` if it is empty.
+    pub fn from_env(var: &str) -> Result<Self, CheckpwnError> {
+        let key = Zeroizing::new(std::env::var(var).map_err(|_| CheckpwnError::MissingApiKey)?);
+
+        Self::new(&key)
+    }
+
+    /// The first and last 4 characters of the key with `***` in between, e.g. `abcd***wxyz`,
+    /// for use in logs and error output where the full key must never appear.
+    /// Keys of fewer than 16 characters are masked completely, as `***`, so that at least half
+    /// of the key is always hidden.
+    pub fn masked(&self) -> String {
+        let len = self.0.chars().count();
+        if len < 16 {
+            return String::from("***");
+        }
+
+        let start: String = self.0.chars().take(4).collect();
+        let end: String = self.0.chars().skip(len - 4).collect();
+        format!("{}***{}", start, end)
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Debug for ApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ApiKey({})", self.masked())
+    }
+}
+
+impl std::str::FromStr for ApiKey {
+    type Err = CheckpwnError;
+
+    fn from_str(key: &str) -> Result<Self, Self::Err> {
+        Self::new(key)
+    }
+}
+
+#[test]
+fn test_api_key_new() {
+    assert_eq!(ApiKey::new("").unwrap_err(), CheckpwnError::EmptyInput);
+    assert_eq!(ApiKey::new("abcdef").unwrap().as_str(), "abcdef");
+}
+
+#[test]
+fn test_api_key_from_env() {
+    std::env::set_var("CHECKPWN_TEST_API_KEY_FROM_ENV", "abcdef");
+    assert_eq!(
+        ApiKey::from_env("CHECKPWN_TEST_API_KEY_FROM_ENV")
+            .unwrap()
+            .as_str(),
+        "abcdef"
+    );
+
+    std::env::set_var("CHECKPWN_TEST_API_KEY_FROM_ENV_EMPTY", "");
+    assert_eq!(
+        ApiKey::from_env("CHECKPWN_TEST_API_KEY_FROM_ENV_EMPTY").unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+
+    assert_eq!(
+        ApiKey::from_env("CHECKPWN_TEST_API_KEY_FROM_ENV_UNSET").unwrap_err(),
+        CheckpwnError::MissingApiKey
+    );
+}
+
+#[test]
+fn test_api_key_debug() {
+    let key = ApiKey::new("abcdefghijklwxyz").unwrap();
+    assert_eq!(format!("{:?}", key), "ApiKey(abcd***wxyz)");
+}
+
+#[test]
+fn test_api_key_masked() {
+    assert_eq!(
+        ApiKey::new("abcdefghijklwxyz").unwrap().masked(),
+        "abcd***wxyz"
+    );
+    // Short keys don't panic and are fully masked
+    assert_eq!(ApiKey::new("abcdefghijklxyz").unwrap().masked(), "***");
+    assert_eq!(ApiKey::new("abcdefghi").unwrap().masked(), "***");
+    assert_eq!(ApiKey::new("a").unwrap().masked(), "***");
+    // Multi-byte characters are not split
+    assert_eq!(
+        ApiKey::new("éééééééééééééééé").unwrap().masked(),
+        "éééé***éééé"
+    );
+    assert_eq!(ApiKey::new("ééééééééé").unwrap().masked(), "***");
+}
+
+#[test]
+fn test_api_key_from_str() {
+    assert_eq!("abcdef".parse::<ApiKey>().unwrap().as_str(), "abcdef");
+    assert_eq!("".parse::<ApiKey>().unwrap_err(), CheckpwnError::EmptyInput);
+}
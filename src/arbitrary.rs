@@ -0,0 +1,81 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! `Arbitrary` implementations for property-based testing with `proptest` or `quickcheck`.
+
+use crate::Password;
+
+/// Maximum length of generated passwords.
+const MAX_LEN: usize = 64;
+
+/// Generates passwords of 1 to 64 printable ASCII characters.
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for Password {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+        use proptest::strategy::Strategy;
+
+        proptest::string::string_regex(&format!("[ -~]{{1,{}}}", MAX_LEN))
+            .expect("the regex is valid")
+            .prop_map(|password| Password::new(&password).expect("the password is not empty"))
+            .boxed()
+    }
+}
+
+/// Generates passwords of 1 to 64 printable ASCII characters.
+#[cfg(feature = "quickcheck")]
+impl quickcheck::Arbitrary for Password {
+    fn arbitrary(g: &mut quickcheck::Gen) -> Self {
+        let len = usize::arbitrary(g) % MAX_LEN + 1;
+        let password: String = (0..len)
+            // The 95 printable ASCII characters start at ' '
+            .map(|_| char::from(b' ' + u8::arbitrary(g) % 95))
+            .collect();
+
+        Password::new(&password).expect("the password is not empty")
+    }
+}
+
+#[cfg(feature = "proptest")]
+proptest::proptest! {
+    #[test]
+    fn test_proptest_password_hash(password: Password) {
+        proptest::prop_assert_eq!(password.hash_prefix().len(), crate::RANGE_PREFIX_LEN);
+        proptest::prop_assert_eq!(
+            Password::from_sha1_hex(password.hash_hex()).unwrap(),
+            password
+        );
+    }
+}
+
+#[cfg(feature = "quickcheck")]
+#[test]
+fn test_quickcheck_password_hash() {
+    let prop: fn(Password) -> bool = |password| {
+        password.hash_prefix().len() == crate::RANGE_PREFIX_LEN
+            && Password::from_sha1_hex(password.hash_hex()).unwrap() == password
+    };
+
+    quickcheck::quickcheck(prop);
+}
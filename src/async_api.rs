@@ -0,0 +1,394 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::CheckpwnError;
+use crate::{
+    api, evaluate_range, CheckpwnClient, Password, CHECKPWN_USER_AGENT, DEFAULT_RATE_LIMIT_DELAY,
+};
+use futures_util::stream::{Stream, StreamExt};
+use std::future::Future;
+use std::sync::{Arc, OnceLock};
+use std::time;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// The client used by the async functions, created on first use. Like `client::default_client`,
+/// it is shared so that every check reuses the same connection pool and TLS context.
+fn shared_client() -> Result<&'static reqwest::Client, CheckpwnError> {
+    static SHARED_CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+
+    if let Some(client) = SHARED_CLIENT.get() {
+        return Ok(client);
+    }
+    let client = reqwest::Client::builder()
+        .connect_timeout(time::Duration::from_secs(10))
+        .user_agent(CHECKPWN_USER_AGENT)
+        .build()
+        .map_err(|_| CheckpwnError::Network)?;
+
+    // If another task built a client first, that one is kept and this one is dropped
+    Ok(SHARED_CLIENT.get_or_init(|| client))
+}
+
+/// Send a GET request and return the status code and body of the response.
+async fn get(
+    client: &reqwest::Client,
+    url: &str,
+    headers: &[(&str, &str)],
+) -> Result<(u16, String), CheckpwnError> {
+    let mut request = client.get(url);
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+
+    let response = request.send().await.map_err(|err| {
+        if err.is_timeout() {
+            CheckpwnError::Timeout
+        } else {
+            CheckpwnError::NetworkError(Arc::new(err))
+        }
+    })?;
+    let status = response.status().as_u16();
+    if status == 429 {
+        let retry_after = response
+            .headers()
+            .get("Retry-After")
+            .and_then(|value| value.to_str().ok());
+        return Err(CheckpwnError::RateLimited(api::parse_retry_after(
+            retry_after,
+        )));
+    }
+    let body = response.text().await.map_err(|_| CheckpwnError::Decoding)?;
+
+    Ok((status, body))
+}
+
+/// Check account, on both account and paste databases, using a given API key.
+/// Before sending a request, the task sleeps for `DEFAULT_RATE_LIMIT_DELAY` without blocking the
+/// executor.
+/// Returns Ok(bool), `bool` indicating whether the account is breached or not.
+/// Err() is returned if an error occurred during the check.
+pub async fn check_account_async(account: &str, api_key: &str) -> Result<bool, CheckpwnError> {
+    if account.is_empty() || api_key.is_empty() {
+        return Err(CheckpwnError::EmptyInput);
+    }
+
+    tokio::time::sleep(DEFAULT_RATE_LIMIT_DELAY).await;
+
+    let acc_db_api_route =
+        api::arg_to_api_route(&api::CheckableChoices::Acc, account, api::HIBP_BASE_URL)?;
+    let paste_db_api_route =
+        api::arg_to_api_route(&api::CheckableChoices::Paste, account, api::HIBP_BASE_URL)?;
+
+    let client = shared_client()?;
+    let headers = [("hibp-api-key", api_key)];
+    let (acc_stat, _) = get(client, &acc_db_api_route, &headers).await?;
+    let (paste_stat, _) = get(client, &paste_db_api_route, &headers).await?;
+
+    api::evaluate_acc_breach_statuscodes(acc_stat, paste_stat)
+}
+
+/// Check password.
+/// Returns Ok(bool), `bool` indicating whether the password is breached or not.
+/// Err() is returned if an error occurred during the check.
+pub async fn check_password_async(password: &Password) -> Result<bool, CheckpwnError> {
+    check_password_async_at(password, api::HIBP_PASS_BASE_URL).await
+}
+
+async fn check_password_async_at(
+    password: &Password,
+    pass_base_url: &str,
+) -> Result<bool, CheckpwnError> {
+    let pass_db_api_route =
+        api::arg_to_api_route(&password.range_choice(), &password.hash, pass_base_url)?;
+
+    let client = shared_client()?;
+    let (request_status, pass_body) =
+        get(client, &pass_db_api_route, &[("Add-Padding", "true")]).await?;
+
+    evaluate_range(request_status, &pass_body, password)
+}
+
+/// Check each of `passwords`, running up to `max_concurrency` checks at the same time.
+/// A `max_concurrency` of 0 is treated as 1. All checks share one HTTP client.
+/// Returns the results in the same order as `passwords`.
+pub async fn check_passwords_concurrent(
+    passwords: &[Password],
+    max_concurrency: usize,
+) -> Vec<Result<bool, CheckpwnError>> {
+    check_passwords_concurrent_at(passwords, max_concurrency, api::HIBP_PASS_BASE_URL).await
+}
+
+async fn check_passwords_concurrent_at(
+    passwords: &[Password],
+    max_concurrency: usize,
+    pass_base_url: &str,
+) -> Vec<Result<bool, CheckpwnError>> {
+    let pass_base_url: Arc<str> = Arc::from(pass_base_url);
+    check_concurrent_with(passwords, max_concurrency, |password| {
+        let pass_base_url = pass_base_url.clone();
+        async move { check_password_async_at(&password, &pass_base_url).await }
+    })
+    .await
+}
+
+async fn check_concurrent_with<F, Fut>(
+    passwords: &[Password],
+    max_concurrency: usize,
+    check: F,
+) -> Vec<Result<bool, CheckpwnError>>
+where
+    F: Fn(Password) -> Fut,
+    Fut: Future<Output = Result<bool, CheckpwnError>> + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(max_concurrency.max(1)));
+    let mut tasks = JoinSet::new();
+
+    for (idx, password) in passwords.iter().enumerate() {
+        let permit = semaphore
+            .clone()
+            .acquire_owned()
+            .await
+            .expect("the semaphore is never closed");
+        let check = check(password.clone());
+
+        tasks.spawn(async move {
+            let res = check.await;
+            drop(permit);
+            (idx, res)
+        });
+    }
+
+    // Every entry is overwritten below, as no task is aborted.
+    let mut results = vec![Err(CheckpwnError::Network); passwords.len()];
+    while let Some(joined) = tasks.join_next().await {
+        match joined {
+            Ok((idx, res)) => results[idx] = res,
+            Err(err) => std::panic::resume_unwind(err.into_panic()),
+        }
+    }
+
+    results
+}
+
+/// Check each password from `passwords` with `client`, yielding the results in the same order.
+/// Passwords are taken from `passwords` one at a time, as the returned stream is polled, so
+/// neither the passwords nor the results are buffered. Each check sends its request with
+/// `CheckpwnClient::check_password` on the blocking thread pool of the runtime, so it does not
+/// block the executor.
+pub fn check_password_stream<S>(
+    passwords: S,
+    client: Arc<CheckpwnClient>,
+) -> impl Stream<Item = Result<bool, CheckpwnError>>
+where
+    S: Stream<Item = Password> + Unpin,
+{
+    passwords.then(move |password| {
+        let client = client.clone();
+        async move {
+            match tokio::task::spawn_blocking(move || client.check_password(&password)).await {
+                Ok(res) => res,
+                Err(err) => std::panic::resume_unwind(err.into_panic()),
+            }
+        }
+    })
+}
+
+#[tokio::test]
+async fn test_empty_input_errors_async() {
+    assert_eq!(
+        check_account_async("", "Test").await.unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    assert_eq!(
+        check_account_async("Test", "").await.unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+}
+
+#[test]
+fn test_shared_client() {
+    let first: *const reqwest::Client = shared_client().unwrap();
+    let second: *const reqwest::Client = shared_client().unwrap();
+    assert_eq!(first, second);
+}
+
+#[tokio::test]
+async fn test_check_concurrent_with() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let running = Arc::new(AtomicUsize::new(0));
+    let max_running = Arc::new(AtomicUsize::new(0));
+    let passwords: Vec<_> = (0..10)
+        .map(|i| {
+            Password::new(if i % 2 == 0 {
+                "qwerty"
+            } else {
+                "dHRUKbDaKgIobOtX"
+            })
+            .unwrap()
+        })
+        .collect();
+
+    let results = check_concurrent_with(&passwords, 3, |password| {
+        let running = running.clone();
+        let max_running = max_running.clone();
+        async move {
+            let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(now_running, Ordering::SeqCst);
+            tokio::time::sleep(time::Duration::from_millis(10)).await;
+            running.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(password.hash_prefix() == "B1B37")
+        }
+    })
+    .await;
+
+    assert_eq!(max_running.load(Ordering::SeqCst), 3);
+    let expected: Vec<_> = (0..10).map(|i| Ok(i % 2 == 0)).collect();
+    assert_eq!(results, expected);
+
+    // No concurrency at all is treated as one check at a time
+    max_running.store(0, Ordering::SeqCst);
+    let results = check_concurrent_with(&passwords[..2], 0, |_| {
+        let running = running.clone();
+        let max_running = max_running.clone();
+        async move {
+            let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+            max_running.fetch_max(now_running, Ordering::SeqCst);
+            tokio::task::yield_now().await;
+            running.fetch_sub(1, Ordering::SeqCst);
+
+            Err(CheckpwnError::Timeout)
+        }
+    })
+    .await;
+    assert_eq!(max_running.load(Ordering::SeqCst), 1);
+    assert_eq!(results, vec![Err(CheckpwnError::Timeout); 2]);
+}
+
+#[tokio::test]
+async fn test_check_passwords_concurrent_in_flight() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::thread;
+
+    let server = Arc::new(tiny_http::Server::http("127.0.0.1:0").unwrap());
+    let in_flight = Arc::new(AtomicUsize::new(0));
+    let max_in_flight = Arc::new(AtomicUsize::new(0));
+
+    let handle = {
+        let server = server.clone();
+        let in_flight = in_flight.clone();
+        let max_in_flight = max_in_flight.clone();
+        thread::spawn(move || {
+            for request in server.incoming_requests() {
+                let in_flight = in_flight.clone();
+                let max_in_flight = max_in_flight.clone();
+                thread::spawn(move || {
+                    let now_in_flight = in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+                    max_in_flight.fetch_max(now_in_flight, Ordering::SeqCst);
+                    thread::sleep(time::Duration::from_millis(50));
+                    in_flight.fetch_sub(1, Ordering::SeqCst);
+
+                    let body = if request.url() == "/range/B1B37" {
+                        "73A05C0ED0176787A4F1574FF0075F7521E:3752262"
+                    } else {
+                        "0000000000000000000000000000000000A:1"
+                    };
+                    let _ = request.respond(tiny_http::Response::from_string(body));
+                });
+            }
+        })
+    };
+    let base_url = format!("http://{}", server.server_addr().to_ip().unwrap());
+
+    let passwords: Vec<_> = (0..8)
+        .map(|i| {
+            Password::new(if i % 2 == 0 {
+                "qwerty"
+            } else {
+                "dHRUKbDaKgIobOtX"
+            })
+            .unwrap()
+        })
+        .collect();
+    let results = check_passwords_concurrent_at(&passwords, 3, &base_url).await;
+
+    let expected: Vec<_> = (0..8).map(|i| Ok(i % 2 == 0)).collect();
+    assert_eq!(results, expected);
+    // The limit is never exceeded, while the exact peak depends on scheduling
+    let max_in_flight = max_in_flight.load(Ordering::SeqCst);
+    assert!(max_in_flight <= 3, "{} requests in flight", max_in_flight);
+    assert!(max_in_flight >= 2, "{} requests in flight", max_in_flight);
+
+    server.unblock();
+    handle.join().unwrap();
+}
+
+#[cfg(feature = "ci_test")]
+#[tokio::test]
+async fn test_check_password_async() {
+    let breached_password = Password::new("qwerty").unwrap();
+    let non_breached_password = Password::new("dHRUKbDaKgIobOtX").unwrap();
+
+    assert!(check_password_async(&breached_password).await.unwrap());
+    assert!(!check_password_async(&non_breached_password).await.unwrap());
+}
+
+#[cfg(feature = "ci_test")]
+#[tokio::test]
+async fn test_check_account_async() {
+    let api_key = std::env::var("API_KEY").unwrap();
+
+    assert!(check_account_async("test@example.com", &api_key)
+        .await
+        .unwrap());
+}
+
+#[tokio::test]
+async fn test_check_password_stream() {
+    let mock = Arc::new(crate::testing::MockHttpClient::new().with_response(
+        "https://api.pwnedpasswords.com/range/B1B37",
+        200,
+        "73678F196DE938F721CD408ED190330F5DB:3\r\n73A05C0ED0176787A4F1574FF0075F7521E:3752262",
+    ));
+    let client = Arc::new(
+        CheckpwnClient::builder()
+            .rate_limit_delay(time::Duration::from_millis(0))
+            .http_client(mock.clone())
+            .build(),
+    );
+    let passwords = futures_util::stream::iter(vec![
+        Password::new("qwerty").unwrap(),
+        Password::new("password").unwrap(),
+        Password::new("qwerty").unwrap(),
+    ]);
+
+    let results: Vec<_> = check_password_stream(passwords, client).collect().await;
+
+    // No response is set for the range of "password"
+    assert_eq!(
+        results,
+        vec![Ok(true), Err(CheckpwnError::Network), Ok(true)]
+    );
+    assert_eq!(mock.calls().len(), 3);
+}
@@ -0,0 +1,132 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::client;
+use crate::errors::CheckpwnError;
+use crate::{ApiKey, Password};
+use std::collections::HashMap;
+
+/// Aggregate statistics over the results of a batch of password checks.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchSummary {
+    /// Number of passwords checked.
+    pub total: usize,
+    /// Number of passwords found to be breached.
+    pub breached: usize,
+    /// Number of passwords not found to be breached.
+    pub clean: usize,
+    /// Number of checks that returned an error.
+    pub errors: usize,
+    /// Number of occurrences of each error, keyed by the error message.
+    pub error_breakdown: HashMap<String, usize>,
+}
+
+impl BatchSummary {
+    /// Fraction of checked passwords that were breached. Returns `0.0` if nothing was checked.
+    pub fn breach_rate(&self) -> f64 {
+        if self.total == 0 {
+            return 0.0;
+        }
+
+        self.breached as f64 / self.total as f64
+    }
+}
+
+/// Check each of `passwords`, returning the results in the same order.
+/// Passwords sharing a hash prefix reuse the range response of the first of them, so each
+/// range is only requested once.
+pub fn check_passwords_batch(passwords: &[Password]) -> Vec<Result<bool, CheckpwnError>> {
+    client::default_client().check_passwords_batch(passwords)
+}
+
+/// Check each of `accounts`, on both account and paste databases, using a given `ApiKey`.
+/// Before each check, the thread waits as set by the rate limit of the default client, which
+/// sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Returns each account together with its result, in the same order as `accounts`.
+pub fn check_accounts_batch(
+    accounts: &[&str],
+    api_key: &ApiKey,
+) -> Vec<(String, Result<bool, CheckpwnError>)> {
+    client::default_client().check_accounts_batch(accounts, api_key)
+}
+
+/// Summarise the results of a batch of password checks.
+pub fn summarise_password_batch_results(
+    results: &[(Password, Result<bool, CheckpwnError>)],
+) -> BatchSummary {
+    let mut summary = BatchSummary {
+        total: results.len(),
+        breached: 0,
+        clean: 0,
+        errors: 0,
+        error_breakdown: HashMap::new(),
+    };
+
+    for (_, result) in results {
+        match result {
+            Ok(true) => summary.breached += 1,
+            Ok(false) => summary.clean += 1,
+            Err(err) => {
+                summary.errors += 1;
+                *summary
+                    .error_breakdown
+                    .entry(err.as_ref().to_string())
+                    .or_insert(0) += 1;
+            }
+        }
+    }
+
+    summary
+}
+
+#[test]
+fn test_summarise_password_batch_results() {
+    let results = vec![
+        (Password::new("qwerty").unwrap(), Ok(true)),
+        (Password::new("password").unwrap(), Ok(true)),
+        (Password::new("dHRUKbDaKgIobOtX").unwrap(), Ok(false)),
+        (Password::new("a").unwrap(), Err(CheckpwnError::Network)),
+        (Password::new("b").unwrap(), Err(CheckpwnError::Network)),
+        (
+            Password::new("c").unwrap(),
+            Err(CheckpwnError::UnexpectedStatus(500)),
+        ),
+    ];
+
+    let summary = summarise_password_batch_results(&results);
+    assert_eq!(summary.total, 6);
+    assert_eq!(summary.breached, 2);
+    assert_eq!(summary.clean, 1);
+    assert_eq!(summary.errors, 3);
+    assert_eq!(summary.error_breakdown.len(), 2);
+    assert_eq!(summary.error_breakdown[CheckpwnError::Network.as_ref()], 2);
+    assert_eq!(
+        summary.error_breakdown[CheckpwnError::UnexpectedStatus(500).as_ref()],
+        1
+    );
+    assert!((summary.breach_rate() - 2.0 / 6.0).abs() < f64::EPSILON);
+
+    let empty = summarise_password_batch_results(&[]);
+    assert_eq!(empty.total, 0);
+    assert!(empty.error_breakdown.is_empty());
+    assert_eq!(empty.breach_rate(), 0.0);
+}
@@ -0,0 +1,713 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::CheckpwnError;
+use crate::{BreachDate, DataClass};
+
+/// Metadata about a breach, as returned by HIBP.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub struct BreachInfo {
+    /// Name of the breach, unique across all breaches. Never changes.
+    pub name: String,
+    /// Domain of the breached service. May be empty.
+    pub domain: String,
+    /// Date the breach occurred. `None` if HIBP gave a date that could not be parsed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "crate::date::deserialize_lenient")
+    )]
+    pub breach_date: Option<BreachDate>,
+    /// Date the breach was added to HIBP. HIBP gives a date and time, of which only the date is
+    /// kept. `None` if HIBP gave a date that could not be parsed.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, deserialize_with = "crate::date::deserialize_lenient")
+    )]
+    pub added_date: Option<BreachDate>,
+    /// Date and time the breach was last modified in HIBP, in ISO 8601 format.
+    pub modified_date: String,
+    /// Number of accounts included in the breach.
+    pub pwn_count: u64,
+    /// Description of the breach, containing HTML markup.
+    pub description: String,
+    /// Kinds of data included in the breach, e.g. "Email addresses" or "Passwords".
+    pub data_classes: Vec<DataClass>,
+    /// Whether the breach is considered verified by HIBP.
+    pub is_verified: bool,
+    /// Whether the breach is considered fabricated by HIBP.
+    pub is_fabricated: bool,
+    /// Whether the breach is considered sensitive by HIBP.
+    pub is_sensitive: bool,
+    /// Whether the breach has been retired from HIBP.
+    pub is_retired: bool,
+    /// Whether the breach is a spam list.
+    pub is_spam_list: bool,
+    /// URI of the logo of the breached service.
+    pub logo_path: String,
+}
+
+/// Data classes ranked by `BreachInfo::most_sensitive_data_class`, most sensitive first.
+const DATA_CLASS_SEVERITY: &[DataClass] = &[
+    DataClass::Passwords,
+    DataClass::CreditCards,
+    DataClass::SocialSecurityNumbers,
+    DataClass::PhoneNumbers,
+    DataClass::EmailAddresses,
+    DataClass::Usernames,
+];
+
+impl BreachInfo {
+    /// Number of whole days since the breach was added to HIBP, or `None` if `added_date` is
+    /// `None`.
+    pub fn age(&self) -> Option<u64> {
+        self.added_date.map(|added_date| added_date.days_ago())
+    }
+
+    /// Whether the breach was added to HIBP within the last `days` days.
+    /// Returns `false` if `added_date` is `None`.
+    pub fn is_recent(&self, days: u64) -> bool {
+        self.added_date
+            .is_some_and(|added_date| added_date.is_within_last_days(days))
+    }
+
+    /// The most sensitive data class included in the breach, ranked as: passwords, credit cards,
+    /// social security numbers, phone numbers, email addresses, usernames.
+    /// Returns `None` if the breach includes none of these.
+    pub fn most_sensitive_data_class(&self) -> Option<&DataClass> {
+        DATA_CLASS_SEVERITY
+            .iter()
+            .find_map(|ranked| self.data_classes.iter().find(|class| *class == ranked))
+    }
+}
+
+/// A list of breaches, e.g. from `get_breach_details`. Derefs to a slice of `BreachInfo`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct BreachList(Vec<BreachInfo>);
+
+impl BreachList {
+    /// The breach with the highest `pwn_count`, or `None` if the list is empty.
+    pub fn most_severe(&self) -> Option<&BreachInfo> {
+        self.0.iter().max_by_key(|breach| breach.pwn_count)
+    }
+
+    /// The breach with the lowest `pwn_count`, or `None` if the list is empty.
+    pub fn least_severe(&self) -> Option<&BreachInfo> {
+        self.0.iter().min_by_key(|breach| breach.pwn_count)
+    }
+
+    /// Sort the breaches by descending `pwn_count`.
+    pub fn sort_by_severity(mut self) -> Self {
+        self.0
+            .sort_by_key(|breach| std::cmp::Reverse(breach.pwn_count));
+        self
+    }
+
+    /// Split the list into spam lists and other breaches, in that order.
+    pub fn partition_spam(self) -> (BreachList, BreachList) {
+        self.0.into_iter().partition(|breach| breach.is_spam_list)
+    }
+}
+
+impl std::ops::Deref for BreachList {
+    type Target = [BreachInfo];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for BreachList {
+    type Item = BreachInfo;
+    type IntoIter = std::vec::IntoIter<BreachInfo>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl std::iter::FromIterator<BreachInfo> for BreachList {
+    fn from_iter<I: IntoIterator<Item = BreachInfo>>(iter: I) -> Self {
+        Self(iter.into_iter().collect())
+    }
+}
+
+impl Extend<BreachInfo> for BreachList {
+    fn extend<I: IntoIterator<Item = BreachInfo>>(&mut self, iter: I) {
+        self.0.extend(iter)
+    }
+}
+
+/// Filter for a list of breaches, e.g. from `get_breach_details`. All conditions added to the
+/// filter must hold for a breach to be kept. The default filter keeps every breach.
+///
+/// # Example:
+/// ```rust
+/// use checkpwn_lib::{BreachFilter, DataClass};
+///
+/// let filter = BreachFilter::new()
+///     .verified_only()
+///     .exclude_spam_lists()
+///     .after_date("2015-01-01")?
+///     .with_data_class(DataClass::Passwords);
+/// assert!(filter.apply(&[]).is_empty());
+/// # Ok::<(), checkpwn_lib::CheckpwnError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BreachFilter {
+    verified_only: bool,
+    exclude_sensitive: bool,
+    exclude_spam_lists: bool,
+    after_date: Option<BreachDate>,
+    data_classes: Vec<DataClass>,
+}
+
+impl BreachFilter {
+    /// Make a new filter that keeps every breach.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only keep breaches HIBP considers verified.
+    pub fn verified_only(mut self) -> Self {
+        self.verified_only = true;
+        self
+    }
+
+    /// Drop breaches HIBP considers sensitive.
+    pub fn exclude_sensitive(mut self) -> Self {
+        self.exclude_sensitive = true;
+        self
+    }
+
+    /// Drop breaches that are spam lists.
+    pub fn exclude_spam_lists(mut self) -> Self {
+        self.exclude_spam_lists = true;
+        self
+    }
+
+    /// Only keep breaches that occurred after `date`, given in `YYYY-MM-DD` format.
+    /// Breaches without a `BreachInfo::breach_date` are dropped.
+    /// `Err(CheckpwnError::InvalidDate)` is returned if `date` is not a valid `YYYY-MM-DD` date.
+    pub fn after_date(mut self, date: &str) -> Result<Self, CheckpwnError> {
+        self.after_date = Some(BreachDate::parse(date).ok_or(CheckpwnError::InvalidDate)?);
+        Ok(self)
+    }
+
+    /// Only keep breaches that include `class`. Can be called multiple times to require several
+    /// data classes.
+    pub fn with_data_class(mut self, class: DataClass) -> Self {
+        self.data_classes.push(class);
+        self
+    }
+
+    /// Whether `breach` passes the filter.
+    pub fn matches(&self, breach: &BreachInfo) -> bool {
+        (!self.verified_only || breach.is_verified)
+            && (!self.exclude_sensitive || !breach.is_sensitive)
+            && (!self.exclude_spam_lists || !breach.is_spam_list)
+            && self.after_date.is_none_or(|date| {
+                breach
+                    .breach_date
+                    .is_some_and(|breach_date| breach_date > date)
+            })
+            && self
+                .data_classes
+                .iter()
+                .all(|class| breach.data_classes.contains(class))
+    }
+
+    /// Keep the breaches of `breaches` that pass the filter, in order.
+    pub fn apply(self, breaches: &[BreachInfo]) -> Vec<&BreachInfo> {
+        breaches
+            .iter()
+            .filter(|breach| self.matches(breach))
+            .collect()
+    }
+}
+
+/// A paste an account appears in, as returned by HIBP.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub struct PasteInfo {
+    /// Paste service the paste was found on, e.g. "Pastebin".
+    pub source: String,
+    /// ID of the paste at the paste service.
+    pub id: Option<String>,
+    /// Title of the paste, if it has one.
+    pub title: Option<String>,
+    /// Date and time the paste was posted, in ISO 8601 format, if known.
+    pub date: Option<String>,
+    /// Number of email addresses found in the paste.
+    pub email_count: u32,
+}
+
+/// Status of the subscription an API key belongs to, as returned by HIBP.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "PascalCase"))]
+pub struct SubscriptionStatus {
+    /// Description of the subscription, including its limits.
+    pub description: String,
+    /// Date and time the subscription ends, in ISO 8601 format.
+    pub subscribed_until: String,
+    /// Number of requests the API key may make per minute.
+    #[cfg_attr(feature = "serde", serde(rename = "Rpm"))]
+    pub rate_limit_request_per_minute: u32,
+}
+
+impl SubscriptionStatus {
+    /// Time to wait between requests to stay within the rate limit of the subscription.
+    /// Returns `None` if the rate limit is 0.
+    pub fn request_delay(&self) -> Option<std::time::Duration> {
+        if self.rate_limit_request_per_minute == 0 {
+            return None;
+        }
+
+        Some(std::time::Duration::from_millis(
+            60_000 / u64::from(self.rate_limit_request_per_minute),
+        ))
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+pub(crate) const SUBSCRIPTION_STATUS_FIXTURE: &str = r#"{
+    "SubscriptionName": "Pwned 1",
+    "Description": "Domains with up to 25 breached addresses each, and a rate limit of 10 per minute",
+    "SubscribedUntil": "2023-09-25T09:30:33",
+    "Rpm": 10,
+    "DomainSearchMaxBreachedAccounts": 25
+}"#;
+
+#[cfg(all(test, feature = "serde"))]
+pub(crate) const BREACH_FIXTURE: &str = r#"{
+    "Name": "Adobe",
+    "Title": "Adobe",
+    "Domain": "adobe.com",
+    "BreachDate": "2013-10-04",
+    "AddedDate": "2013-12-04T00:00Z",
+    "ModifiedDate": "2022-05-15T23:52:49Z",
+    "PwnCount": 152445165,
+    "Description": "In October 2013, 153 million Adobe accounts were breached with each containing an internal ID, username, email, <em>encrypted</em> password and a password hint in plain text.",
+    "DataClasses": ["Email addresses", "Password hints", "Passwords", "Usernames"],
+    "IsVerified": true,
+    "IsFabricated": false,
+    "IsSensitive": false,
+    "IsRetired": false,
+    "IsSpamList": false,
+    "IsMalware": false,
+    "IsSubscriptionFree": false,
+    "LogoPath": "https://haveibeenpwned.com/Content/Images/PwnedLogos/Adobe.png"
+}"#;
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_deserialize_breach_info() {
+    let breaches: BreachList = serde_json::from_str(&format!("[{}]", BREACH_FIXTURE)).unwrap();
+    assert_eq!(breaches.len(), 1);
+
+    let adobe = &breaches[0];
+    assert_eq!(adobe.name, "Adobe");
+    assert_eq!(adobe.domain, "adobe.com");
+    assert_eq!(adobe.breach_date, BreachDate::parse("2013-10-04"));
+    assert_eq!(adobe.added_date, BreachDate::parse("2013-12-04"));
+    assert_eq!(adobe.modified_date, "2022-05-15T23:52:49Z");
+    assert_eq!(adobe.pwn_count, 152445165);
+    assert!(adobe.description.starts_with("In October 2013"));
+    assert_eq!(
+        adobe.data_classes,
+        vec![
+            DataClass::EmailAddresses,
+            DataClass::PasswordHints,
+            DataClass::Passwords,
+            DataClass::Usernames
+        ]
+    );
+    assert!(adobe.is_verified);
+    assert!(!adobe.is_fabricated);
+    assert!(!adobe.is_sensitive);
+    assert!(!adobe.is_retired);
+    assert!(!adobe.is_spam_list);
+    assert_eq!(
+        adobe.logo_path,
+        "https://haveibeenpwned.com/Content/Images/PwnedLogos/Adobe.png"
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_deserialize_breach_info_missing_field() {
+    assert!(serde_json::from_str::<BreachInfo>(r#"{ "Name": "Adobe" }"#).is_err());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_deserialize_paste_info() {
+    let pastes: Vec<PasteInfo> = serde_json::from_str(
+        r#"[
+            {
+                "Source": "Pastebin",
+                "Id": "8Q0BvKD8",
+                "Title": "syslog",
+                "Date": "2014-03-04T19:14:54Z",
+                "EmailCount": 139
+            },
+            {
+                "Source": "Pastie",
+                "Id": "7152479",
+                "Date": "2013-03-28T16:51:10Z",
+                "EmailCount": 30
+            },
+            {
+                "Source": "AdHocUrl",
+                "Id": "http://example.com/paste.txt",
+                "Title": null,
+                "Date": null,
+                "EmailCount": 2
+            }
+        ]"#,
+    )
+    .unwrap();
+
+    assert_eq!(pastes.len(), 3);
+    assert_eq!(
+        pastes[0],
+        PasteInfo {
+            source: "Pastebin".to_string(),
+            id: Some("8Q0BvKD8".to_string()),
+            title: Some("syslog".to_string()),
+            date: Some("2014-03-04T19:14:54Z".to_string()),
+            email_count: 139,
+        }
+    );
+    assert_eq!(pastes[1].title, None);
+    assert_eq!(pastes[1].email_count, 30);
+    assert_eq!(pastes[2].source, "AdHocUrl");
+    assert_eq!(pastes[2].title, None);
+    assert_eq!(pastes[2].date, None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_deserialize_subscription_status() {
+    let status: SubscriptionStatus = serde_json::from_str(SUBSCRIPTION_STATUS_FIXTURE).unwrap();
+
+    assert_eq!(
+        status.description,
+        "Domains with up to 25 breached addresses each, and a rate limit of 10 per minute"
+    );
+    assert_eq!(status.subscribed_until, "2023-09-25T09:30:33");
+    assert_eq!(status.rate_limit_request_per_minute, 10);
+    assert_eq!(
+        status.request_delay(),
+        Some(std::time::Duration::from_millis(6000))
+    );
+}
+
+#[test]
+fn test_subscription_status_request_delay() {
+    let mut status = SubscriptionStatus {
+        description: String::new(),
+        subscribed_until: String::new(),
+        rate_limit_request_per_minute: 1000,
+    };
+    assert_eq!(
+        status.request_delay(),
+        Some(std::time::Duration::from_millis(60))
+    );
+
+    status.rate_limit_request_per_minute = 0;
+    assert_eq!(status.request_delay(), None);
+}
+
+#[cfg(test)]
+fn test_breach(name: &str, breach_date: &str, data_classes: Vec<DataClass>) -> BreachInfo {
+    BreachInfo {
+        name: name.to_string(),
+        domain: String::new(),
+        breach_date: BreachDate::parse(breach_date),
+        added_date: None,
+        modified_date: String::new(),
+        pwn_count: 0,
+        description: String::new(),
+        data_classes,
+        is_verified: true,
+        is_fabricated: false,
+        is_sensitive: false,
+        is_retired: false,
+        is_spam_list: false,
+        logo_path: String::new(),
+    }
+}
+
+#[test]
+fn test_breach_info_age() {
+    let mut breach = test_breach("Old", "2013-10-04", vec![]);
+    assert_eq!(breach.age(), None);
+    assert!(!breach.is_recent(u64::MAX));
+
+    breach.added_date = BreachDate::parse("2013-12-04");
+    let age = breach.age().unwrap();
+    assert!(age > 4000);
+    assert!(breach.is_recent(age));
+    assert!(!breach.is_recent(age - 1));
+    assert!(!breach.is_recent(30));
+
+    // Added in the future, e.g. due to clock skew
+    breach.added_date = BreachDate::parse("9999-12-31");
+    assert_eq!(breach.age(), Some(0));
+    assert!(breach.is_recent(0));
+}
+
+#[test]
+fn test_most_sensitive_data_class() {
+    let breach = test_breach(
+        "Adobe",
+        "2013-10-04",
+        vec![
+            DataClass::EmailAddresses,
+            DataClass::PasswordHints,
+            DataClass::Passwords,
+            DataClass::Usernames,
+        ],
+    );
+    assert_eq!(
+        breach.most_sensitive_data_class(),
+        Some(&DataClass::Passwords)
+    );
+
+    let breach = test_breach(
+        "Shop",
+        "2019-01-01",
+        vec![
+            DataClass::Usernames,
+            DataClass::PhoneNumbers,
+            DataClass::CreditCards,
+            DataClass::SocialSecurityNumbers,
+        ],
+    );
+    assert_eq!(
+        breach.most_sensitive_data_class(),
+        Some(&DataClass::CreditCards)
+    );
+
+    let breach = test_breach(
+        "Forum",
+        "2019-01-01",
+        vec![DataClass::Usernames, DataClass::EmailAddresses],
+    );
+    assert_eq!(
+        breach.most_sensitive_data_class(),
+        Some(&DataClass::EmailAddresses)
+    );
+
+    // Unranked data classes are ignored
+    let breach = test_breach(
+        "Scrape",
+        "2019-01-01",
+        vec![DataClass::Names, DataClass::Other("Hobbies".to_string())],
+    );
+    assert_eq!(breach.most_sensitive_data_class(), None);
+    assert_eq!(
+        test_breach("Empty", "2019-01-01", vec![]).most_sensitive_data_class(),
+        None
+    );
+}
+
+#[test]
+fn test_breach_filter() {
+    let mut unverified = test_breach("Unverified", "2019-01-01", vec![DataClass::Passwords]);
+    unverified.is_verified = false;
+    let mut sensitive = test_breach("Sensitive", "2019-01-01", vec![DataClass::Passwords]);
+    sensitive.is_sensitive = true;
+    let mut spam = test_breach("Spam", "2019-01-01", vec![DataClass::EmailAddresses]);
+    spam.is_spam_list = true;
+    let old = test_breach(
+        "Old",
+        "2012-07-01",
+        vec![DataClass::EmailAddresses, DataClass::Passwords],
+    );
+    let recent = test_breach(
+        "Recent",
+        "2020-03-15",
+        vec![DataClass::EmailAddresses, DataClass::Passwords],
+    );
+    let breaches = vec![unverified, sensitive, spam, old, recent];
+
+    let names = |filter: BreachFilter| -> Vec<&str> {
+        filter
+            .apply(&breaches)
+            .iter()
+            .map(|breach| breach.name.as_str())
+            .collect()
+    };
+
+    assert_eq!(names(BreachFilter::new()).len(), 5);
+    assert_eq!(
+        names(BreachFilter::new().verified_only()),
+        vec!["Sensitive", "Spam", "Old", "Recent"]
+    );
+    assert_eq!(
+        names(BreachFilter::new().exclude_sensitive().exclude_spam_lists()),
+        vec!["Unverified", "Old", "Recent"]
+    );
+    assert_eq!(
+        names(BreachFilter::new().after_date("2019-01-01").unwrap()),
+        vec!["Recent"]
+    );
+    assert_eq!(
+        names(
+            BreachFilter::new()
+                .with_data_class(DataClass::EmailAddresses)
+                .with_data_class(DataClass::Passwords)
+        ),
+        vec!["Old", "Recent"]
+    );
+    assert_eq!(
+        names(
+            BreachFilter::new()
+                .verified_only()
+                .exclude_sensitive()
+                .exclude_spam_lists()
+                .after_date("2015-01-01")
+                .unwrap()
+                .with_data_class(DataClass::Passwords)
+        ),
+        vec!["Recent"]
+    );
+
+    for invalid in ["2015-1-1", "2015/01/01", "01-01-2015", "2015-02-30", ""].iter() {
+        assert_eq!(
+            BreachFilter::new().after_date(invalid),
+            Err(CheckpwnError::InvalidDate),
+            "{:?}",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_breach_list() {
+    let mut spam = test_breach("Spam", "2019-01-01", vec![]);
+    spam.is_spam_list = true;
+    spam.pwn_count = 500;
+    let mut small = test_breach("Small", "2019-01-01", vec![]);
+    small.pwn_count = 10;
+    let mut large = test_breach("Large", "2019-01-01", vec![]);
+    large.pwn_count = 1000;
+
+    let list: BreachList = vec![spam, small, large].into_iter().collect();
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.most_severe().unwrap().name, "Large");
+    assert_eq!(list.least_severe().unwrap().name, "Small");
+
+    let sorted = list.sort_by_severity();
+    let names: Vec<_> = sorted.iter().map(|breach| breach.name.as_str()).collect();
+    assert_eq!(names, vec!["Large", "Spam", "Small"]);
+
+    let (spam_lists, others) = sorted.partition_spam();
+    assert_eq!(spam_lists.len(), 1);
+    assert_eq!(spam_lists[0].name, "Spam");
+    let names: Vec<_> = others.into_iter().map(|breach| breach.name).collect();
+    assert_eq!(names, vec!["Large", "Small"]);
+
+    let empty = BreachList::default();
+    assert!(empty.most_severe().is_none());
+    assert!(empty.least_severe().is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_breach_info_round_trip() {
+    let adobe: BreachInfo = serde_json::from_str(BREACH_FIXTURE).unwrap();
+    let json = serde_json::to_value(&adobe).unwrap();
+
+    // Fields are serialized with the same names as HIBP uses
+    assert_eq!(json["Name"], "Adobe");
+    assert_eq!(json["BreachDate"], "2013-10-04");
+    assert_eq!(json["PwnCount"], 152445165);
+    assert_eq!(
+        json["DataClasses"],
+        serde_json::json!([
+            "Email addresses",
+            "Password hints",
+            "Passwords",
+            "Usernames"
+        ])
+    );
+    assert_eq!(json["IsSpamList"], false);
+    assert_eq!(serde_json::from_value::<BreachInfo>(json).unwrap(), adobe);
+
+    let list: BreachList = serde_json::from_str(&format!("[{}]", BREACH_FIXTURE)).unwrap();
+    let json = serde_json::to_string(&list).unwrap();
+    assert_eq!(serde_json::from_str::<BreachList>(&json).unwrap(), list);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_paste_info_round_trip() {
+    let pastes: Vec<PasteInfo> = serde_json::from_str(
+        r#"[
+            {
+                "Source": "Pastebin",
+                "Id": "8Q0BvKD8",
+                "Title": "syslog",
+                "Date": "2014-03-04T19:14:54Z",
+                "EmailCount": 139
+            },
+            {
+                "Source": "Pastie",
+                "Id": "7152479",
+                "Date": "2013-03-28T16:51:10Z",
+                "EmailCount": 30
+            }
+        ]"#,
+    )
+    .unwrap();
+
+    let json = serde_json::to_value(&pastes).unwrap();
+    assert_eq!(json[0]["Source"], "Pastebin");
+    assert_eq!(json[0]["EmailCount"], 139);
+    assert_eq!(json[1]["Title"], serde_json::Value::Null);
+    assert_eq!(
+        serde_json::from_value::<Vec<PasteInfo>>(json).unwrap(),
+        pastes
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_subscription_status_round_trip() {
+    let status: SubscriptionStatus = serde_json::from_str(SUBSCRIPTION_STATUS_FIXTURE).unwrap();
+    let json = serde_json::to_value(&status).unwrap();
+
+    assert_eq!(json["Rpm"], 10);
+    assert_eq!(json["SubscribedUntil"], "2023-09-25T09:30:33");
+    assert_eq!(
+        serde_json::from_value::<SubscriptionStatus>(json).unwrap(),
+        status
+    );
+}
@@ -0,0 +1,109 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::num::NonZeroUsize;
+
+/// Cache of range responses from HIBP, set with `CheckpwnClientBuilder::range_cache`.
+/// Passwords sharing a hash prefix are then checked against the cached range, without
+/// requesting it again. Once full, the least recently used range is evicted.
+///
+/// Ranges are keyed by the hash prefix, with `?mode=ntlm` appended for NTLM hashes, and stored
+/// together with their `ETag`, if HIBP sent one.
+#[derive(Debug)]
+pub struct RangeCache(lru::LruCache<String, (String, Option<String>)>, bool);
+
+impl RangeCache {
+    /// Make a new, empty `RangeCache` holding up to `capacity` ranges.
+    /// A `capacity` of 0 is treated as 1.
+    pub fn new(capacity: usize) -> Self {
+        Self(
+            lru::LruCache::new(NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN)),
+            false,
+        )
+    }
+
+    /// Whether to revalidate cached ranges that have an `ETag` before each use, by requesting them
+    /// with `If-None-Match`. HIBP then only sends the range again if it has changed, and responds
+    /// with an empty 304 Not Modified otherwise. Cached ranges are not revalidated by default.
+    pub fn revalidate(mut self, revalidate: bool) -> Self {
+        self.1 = revalidate;
+        self
+    }
+
+    /// Whether cached ranges are revalidated, as set with `revalidate`.
+    pub fn revalidates(&self) -> bool {
+        self.1
+    }
+
+    /// Number of ranges in the cache.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the cache holds no ranges.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Remove all ranges from the cache.
+    pub fn clear(&mut self) {
+        self.0.clear()
+    }
+
+    /// The body and `ETag` of the range for `key`, marking it as recently used.
+    pub(crate) fn get(&mut self, key: &str) -> Option<&(String, Option<String>)> {
+        self.0.get(key)
+    }
+
+    /// Store the body and `ETag` of the range for `key`.
+    pub(crate) fn insert(&mut self, key: String, body: String, etag: Option<String>) {
+        self.0.put(key, (body, etag));
+    }
+}
+
+#[test]
+fn test_range_cache_eviction() {
+    let mut cache = RangeCache::new(2);
+    assert!(cache.is_empty());
+
+    cache.insert("B1B37".to_string(), "first".to_string(), None);
+    cache.insert("8846F".to_string(), "second".to_string(), None);
+    // Mark the first range as recently used, so the second one is evicted
+    assert_eq!(cache.get("B1B37").unwrap().0, "first");
+    cache.insert(
+        "5BAA6".to_string(),
+        "third".to_string(),
+        Some("etag".to_string()),
+    );
+
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get("8846F").is_none());
+    assert_eq!(
+        cache.get("5BAA6"),
+        Some(&("third".to_string(), Some("etag".to_string())))
+    );
+
+    cache.clear();
+    assert!(cache.is_empty());
+
+    assert_eq!(RangeCache::new(0).0.cap().get(), 1);
+}
@@ -0,0 +1,112 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::api;
+use crate::Password;
+use std::collections::HashSet;
+
+/// A set of common passwords, to check passwords against locally without sending any requests.
+/// The passwords are hashed once, when the set is built.
+#[derive(Debug, Clone, Default)]
+pub struct CommonPasswordSet {
+    hashes: HashSet<[u8; 20]>,
+}
+
+impl CommonPasswordSet {
+    /// Hash and collect each of `list`. Empty entries are ignored.
+    pub fn from_plaintexts(list: &[&str]) -> Self {
+        Self {
+            hashes: list
+                .iter()
+                .filter(|p| !p.is_empty())
+                .filter_map(|p| hex_to_digest(&api::hash_password(p)))
+                .collect(),
+        }
+    }
+
+    /// Returns `true` if `password` is in the set.
+    pub fn contains(&self, password: &Password) -> bool {
+        match hex_to_digest(&password.hash) {
+            Some(digest) => self.hashes.contains(&digest),
+            None => false,
+        }
+    }
+
+    /// Number of distinct passwords in the set.
+    pub fn len(&self) -> usize {
+        self.hashes.len()
+    }
+
+    /// Returns `true` if the set contains no passwords.
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+}
+
+fn hex_to_digest(hash: &str) -> Option<[u8; 20]> {
+    let mut digest = [0u8; 20];
+    hex::decode_to_slice(hash, &mut digest).ok()?;
+
+    Some(digest)
+}
+
+/// Check whether `password` is one of `common_list`, without sending any requests.
+/// This hashes every entry of `common_list` on each call. When checking several passwords
+/// against the same list, build a `CommonPasswordSet` once instead.
+pub fn detect_common_password(password: &Password, common_list: &[&str]) -> bool {
+    CommonPasswordSet::from_plaintexts(common_list).contains(password)
+}
+
+#[test]
+fn test_common_password_set() {
+    let set = CommonPasswordSet::from_plaintexts(&["password", "qwerty", "", "qwerty"]);
+    assert_eq!(set.len(), 2);
+
+    assert!(set.contains(&Password::new("password").unwrap()));
+    assert!(set.contains(&Password::new("qwerty").unwrap()));
+    assert!(!set.contains(&Password::new("QWERTY").unwrap()));
+    assert!(!set.contains(&Password::new("dHRUKbDaKgIobOtX").unwrap()));
+
+    assert!(CommonPasswordSet::from_plaintexts(&[]).is_empty());
+}
+
+#[test]
+fn test_detect_common_password() {
+    let common = ["123456", "password", "qwerty"];
+
+    assert!(detect_common_password(
+        &Password::new("password").unwrap(),
+        &common
+    ));
+    assert!(detect_common_password(
+        &Password::new("qwerty").unwrap(),
+        &common
+    ));
+    assert!(!detect_common_password(
+        &Password::new("dHRUKbDaKgIobOtX").unwrap(),
+        &common
+    ));
+    assert!(!detect_common_password(
+        &Password::new("qwerty").unwrap(),
+        &[]
+    ));
+}
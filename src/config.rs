@@ -0,0 +1,123 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::client::{DEFAULT_CONNECT_TIMEOUT, DEFAULT_RATE_LIMIT_DELAY, DEFAULT_READ_TIMEOUT};
+use crate::CHECKPWN_USER_AGENT;
+use std::time::Duration;
+
+/// Configuration of a `CheckpwnClient`, applied with `CheckpwnClientBuilder::from_config`.
+///
+/// Use `CheckpwnConfig::from_env` to configure a client through environment variables,
+/// e.g. in containerized deployments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckpwnConfig {
+    /// Timeout for connecting to HIBP. Defaults to `DEFAULT_CONNECT_TIMEOUT`.
+    pub connect_timeout: Duration,
+    /// Timeout for reading a response from HIBP. Defaults to `DEFAULT_READ_TIMEOUT`.
+    pub read_timeout: Duration,
+    /// Time to sleep before each account check. Defaults to `DEFAULT_RATE_LIMIT_DELAY`.
+    pub rate_limit_delay: Duration,
+    /// Whether to ask HIBP to pad range responses. Defaults to `true`.
+    pub padding_enabled: bool,
+    /// Whether account checks include breaches HIBP has flagged as unverified. Defaults to `true`.
+    pub include_unverified: bool,
+    /// UserAgent sent to HIBP. Defaults to `CHECKPWN_USER_AGENT`.
+    pub user_agent: String,
+}
+
+impl Default for CheckpwnConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            rate_limit_delay: DEFAULT_RATE_LIMIT_DELAY,
+            padding_enabled: true,
+            include_unverified: true,
+            user_agent: CHECKPWN_USER_AGENT.to_string(),
+        }
+    }
+}
+
+impl CheckpwnConfig {
+    /// Make a new `CheckpwnConfig` from the following environment variables:
+    ///
+    /// - `CHECKPWN_CONNECT_TIMEOUT_SECS`: `connect_timeout`, in seconds.
+    /// - `CHECKPWN_READ_TIMEOUT_SECS`: `read_timeout`, in seconds.
+    /// - `CHECKPWN_RATE_LIMIT_MS`: `rate_limit_delay`, in milliseconds.
+    /// - `CHECKPWN_USER_AGENT`: `user_agent`.
+    ///
+    /// The default is used for any variable that is unset or can't be parsed, as well as for
+    /// `padding_enabled` and `include_unverified`.
+    pub fn from_env() -> Self {
+        Self::from_vars(|name| std::env::var(name).ok())
+    }
+
+    fn from_vars<F: Fn(&str) -> Option<String>>(var: F) -> Self {
+        let parse = |name: &str| var(name).and_then(|value| value.trim().parse::<u64>().ok());
+        let default = Self::default();
+
+        Self {
+            connect_timeout: parse("CHECKPWN_CONNECT_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.connect_timeout),
+            read_timeout: parse("CHECKPWN_READ_TIMEOUT_SECS")
+                .map(Duration::from_secs)
+                .unwrap_or(default.read_timeout),
+            rate_limit_delay: parse("CHECKPWN_RATE_LIMIT_MS")
+                .map(Duration::from_millis)
+                .unwrap_or(default.rate_limit_delay),
+            user_agent: var("CHECKPWN_USER_AGENT")
+                .filter(|user_agent| !user_agent.is_empty())
+                .unwrap_or(default.user_agent),
+            ..default
+        }
+    }
+}
+
+#[test]
+fn test_config_from_vars() {
+    assert_eq!(
+        CheckpwnConfig::from_vars(|_| None),
+        CheckpwnConfig::default()
+    );
+
+    let config = CheckpwnConfig::from_vars(|name| {
+        match name {
+            "CHECKPWN_CONNECT_TIMEOUT_SECS" => Some("5"),
+            "CHECKPWN_READ_TIMEOUT_SECS" => Some("not a number"),
+            "CHECKPWN_RATE_LIMIT_MS" => Some("2000"),
+            "CHECKPWN_USER_AGENT" => Some("custom agent"),
+            _ => None,
+        }
+        .map(String::from)
+    });
+    assert_eq!(
+        config,
+        CheckpwnConfig {
+            connect_timeout: Duration::from_secs(5),
+            read_timeout: DEFAULT_READ_TIMEOUT,
+            rate_limit_delay: Duration::from_millis(2000),
+            user_agent: "custom agent".to_string(),
+            ..CheckpwnConfig::default()
+        }
+    );
+}
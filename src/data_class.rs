@@ -0,0 +1,421 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// A kind of data included in a breach, as named by HIBP, e.g. "Email addresses".
+///
+/// Data classes that HIBP adds after this list was written are kept as `DataClass::Other`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(from = "String"))]
+pub enum DataClass {
+    /// "Account balances"
+    AccountBalances,
+    /// "Ages"
+    Ages,
+    /// "Auth tokens"
+    AuthTokens,
+    /// "Avatars"
+    Avatars,
+    /// "Bank account numbers"
+    BankAccountNumbers,
+    /// "Biometric data"
+    BiometricData,
+    /// "Browser user agent details"
+    BrowserUserAgentDetails,
+    /// "Chat logs"
+    ChatLogs,
+    /// "Credit card CVV"
+    CreditCardCvv,
+    /// "Credit cards"
+    CreditCards,
+    /// "Criminal convictions"
+    CriminalConvictions,
+    /// "Dates of birth"
+    DatesOfBirth,
+    /// "Device information"
+    DeviceInformation,
+    /// "Driver's licenses"
+    DriversLicenses,
+    /// "Education levels"
+    EducationLevels,
+    /// "Email addresses"
+    EmailAddresses,
+    /// "Email messages"
+    EmailMessages,
+    /// "Employers"
+    Employers,
+    /// "Encrypted keys"
+    EncryptedKeys,
+    /// "Ethnicities"
+    Ethnicities,
+    /// "Genders"
+    Genders,
+    /// "Geographic locations"
+    GeographicLocations,
+    /// "Government issued IDs"
+    GovernmentIssuedIds,
+    /// "Historical passwords"
+    HistoricalPasswords,
+    /// "Income levels"
+    IncomeLevels,
+    /// "Instant messenger identities"
+    InstantMessengerIdentities,
+    /// "IP addresses"
+    IpAddresses,
+    /// "Job titles"
+    JobTitles,
+    /// "Marital statuses"
+    MaritalStatuses,
+    /// "Names"
+    Names,
+    /// "Nationalities"
+    Nationalities,
+    /// "Nicknames"
+    Nicknames,
+    /// "Occupations"
+    Occupations,
+    /// "Partial credit card data"
+    PartialCreditCardData,
+    /// "Passport numbers"
+    PassportNumbers,
+    /// "Password hints"
+    PasswordHints,
+    /// "Passwords"
+    Passwords,
+    /// "Payment histories"
+    PaymentHistories,
+    /// "Personal health data"
+    PersonalHealthData,
+    /// "Phone numbers"
+    PhoneNumbers,
+    /// "Physical addresses"
+    PhysicalAddresses,
+    /// "PINs"
+    Pins,
+    /// "Political views"
+    PoliticalViews,
+    /// "Private messages"
+    PrivateMessages,
+    /// "Profile photos"
+    ProfilePhotos,
+    /// "Purchases"
+    Purchases,
+    /// "Recovery email addresses"
+    RecoveryEmailAddresses,
+    /// "Religions"
+    Religions,
+    /// "Security questions and answers"
+    SecurityQuestionsAndAnswers,
+    /// "Sexual orientations"
+    SexualOrientations,
+    /// "SMS messages"
+    SmsMessages,
+    /// "Social media profiles"
+    SocialMediaProfiles,
+    /// "Social security numbers"
+    SocialSecurityNumbers,
+    /// "Spoken languages"
+    SpokenLanguages,
+    /// "Time zones"
+    TimeZones,
+    /// "Usernames"
+    Usernames,
+    /// "Website activity"
+    WebsiteActivity,
+    /// Any data class not listed above, with its name as returned by HIBP.
+    Other(String),
+}
+
+impl DataClass {
+    /// Every data class except `DataClass::Other`.
+    const KNOWN: &'static [DataClass] = &[
+        DataClass::AccountBalances,
+        DataClass::Ages,
+        DataClass::AuthTokens,
+        DataClass::Avatars,
+        DataClass::BankAccountNumbers,
+        DataClass::BiometricData,
+        DataClass::BrowserUserAgentDetails,
+        DataClass::ChatLogs,
+        DataClass::CreditCardCvv,
+        DataClass::CreditCards,
+        DataClass::CriminalConvictions,
+        DataClass::DatesOfBirth,
+        DataClass::DeviceInformation,
+        DataClass::DriversLicenses,
+        DataClass::EducationLevels,
+        DataClass::EmailAddresses,
+        DataClass::EmailMessages,
+        DataClass::Employers,
+        DataClass::EncryptedKeys,
+        DataClass::Ethnicities,
+        DataClass::Genders,
+        DataClass::GeographicLocations,
+        DataClass::GovernmentIssuedIds,
+        DataClass::HistoricalPasswords,
+        DataClass::IncomeLevels,
+        DataClass::InstantMessengerIdentities,
+        DataClass::IpAddresses,
+        DataClass::JobTitles,
+        DataClass::MaritalStatuses,
+        DataClass::Names,
+        DataClass::Nationalities,
+        DataClass::Nicknames,
+        DataClass::Occupations,
+        DataClass::PartialCreditCardData,
+        DataClass::PassportNumbers,
+        DataClass::PasswordHints,
+        DataClass::Passwords,
+        DataClass::PaymentHistories,
+        DataClass::PersonalHealthData,
+        DataClass::PhoneNumbers,
+        DataClass::PhysicalAddresses,
+        DataClass::Pins,
+        DataClass::PoliticalViews,
+        DataClass::PrivateMessages,
+        DataClass::ProfilePhotos,
+        DataClass::Purchases,
+        DataClass::RecoveryEmailAddresses,
+        DataClass::Religions,
+        DataClass::SecurityQuestionsAndAnswers,
+        DataClass::SexualOrientations,
+        DataClass::SmsMessages,
+        DataClass::SocialMediaProfiles,
+        DataClass::SocialSecurityNumbers,
+        DataClass::SpokenLanguages,
+        DataClass::TimeZones,
+        DataClass::Usernames,
+        DataClass::WebsiteActivity,
+    ];
+
+    /// The name of the data class, as used by HIBP.
+    pub fn as_str(&self) -> &str {
+        match self {
+            DataClass::AccountBalances => "Account balances",
+            DataClass::Ages => "Ages",
+            DataClass::AuthTokens => "Auth tokens",
+            DataClass::Avatars => "Avatars",
+            DataClass::BankAccountNumbers => "Bank account numbers",
+            DataClass::BiometricData => "Biometric data",
+            DataClass::BrowserUserAgentDetails => "Browser user agent details",
+            DataClass::ChatLogs => "Chat logs",
+            DataClass::CreditCardCvv => "Credit card CVV",
+            DataClass::CreditCards => "Credit cards",
+            DataClass::CriminalConvictions => "Criminal convictions",
+            DataClass::DatesOfBirth => "Dates of birth",
+            DataClass::DeviceInformation => "Device information",
+            DataClass::DriversLicenses => "Driver's licenses",
+            DataClass::EducationLevels => "Education levels",
+            DataClass::EmailAddresses => "Email addresses",
+            DataClass::EmailMessages => "Email messages",
+            DataClass::Employers => "Employers",
+            DataClass::EncryptedKeys => "Encrypted keys",
+            DataClass::Ethnicities => "Ethnicities",
+            DataClass::Genders => "Genders",
+            DataClass::GeographicLocations => "Geographic locations",
+            DataClass::GovernmentIssuedIds => "Government issued IDs",
+            DataClass::HistoricalPasswords => "Historical passwords",
+            DataClass::IncomeLevels => "Income levels",
+            DataClass::InstantMessengerIdentities => "Instant messenger identities",
+            DataClass::IpAddresses => "IP addresses",
+            DataClass::JobTitles => "Job titles",
+            DataClass::MaritalStatuses => "Marital statuses",
+            DataClass::Names => "Names",
+            DataClass::Nationalities => "Nationalities",
+            DataClass::Nicknames => "Nicknames",
+            DataClass::Occupations => "Occupations",
+            DataClass::PartialCreditCardData => "Partial credit card data",
+            DataClass::PassportNumbers => "Passport numbers",
+            DataClass::PasswordHints => "Password hints",
+            DataClass::Passwords => "Passwords",
+            DataClass::PaymentHistories => "Payment histories",
+            DataClass::PersonalHealthData => "Personal health data",
+            DataClass::PhoneNumbers => "Phone numbers",
+            DataClass::PhysicalAddresses => "Physical addresses",
+            DataClass::Pins => "PINs",
+            DataClass::PoliticalViews => "Political views",
+            DataClass::PrivateMessages => "Private messages",
+            DataClass::ProfilePhotos => "Profile photos",
+            DataClass::Purchases => "Purchases",
+            DataClass::RecoveryEmailAddresses => "Recovery email addresses",
+            DataClass::Religions => "Religions",
+            DataClass::SecurityQuestionsAndAnswers => "Security questions and answers",
+            DataClass::SexualOrientations => "Sexual orientations",
+            DataClass::SmsMessages => "SMS messages",
+            DataClass::SocialMediaProfiles => "Social media profiles",
+            DataClass::SocialSecurityNumbers => "Social security numbers",
+            DataClass::SpokenLanguages => "Spoken languages",
+            DataClass::TimeZones => "Time zones",
+            DataClass::Usernames => "Usernames",
+            DataClass::WebsiteActivity => "Website activity",
+            DataClass::Other(name) => name,
+        }
+    }
+}
+
+impl std::str::FromStr for DataClass {
+    type Err = std::convert::Infallible;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Ok(Self::KNOWN
+            .iter()
+            .find(|class| class.as_str() == name)
+            .cloned()
+            .unwrap_or_else(|| DataClass::Other(name.to_string())))
+    }
+}
+
+impl From<String> for DataClass {
+    fn from(name: String) -> Self {
+        match name.parse() {
+            Ok(class) => class,
+            Err(never) => match never {},
+        }
+    }
+}
+
+/// Serialized as the name HIBP uses, e.g. "Email addresses".
+#[cfg(feature = "serde")]
+impl serde::Serialize for DataClass {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl std::fmt::Display for DataClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+#[test]
+fn test_data_class_names() {
+    let expected = [
+        ("Account balances", DataClass::AccountBalances),
+        ("Ages", DataClass::Ages),
+        ("Auth tokens", DataClass::AuthTokens),
+        ("Avatars", DataClass::Avatars),
+        ("Bank account numbers", DataClass::BankAccountNumbers),
+        ("Biometric data", DataClass::BiometricData),
+        (
+            "Browser user agent details",
+            DataClass::BrowserUserAgentDetails,
+        ),
+        ("Chat logs", DataClass::ChatLogs),
+        ("Credit card CVV", DataClass::CreditCardCvv),
+        ("Credit cards", DataClass::CreditCards),
+        ("Criminal convictions", DataClass::CriminalConvictions),
+        ("Dates of birth", DataClass::DatesOfBirth),
+        ("Device information", DataClass::DeviceInformation),
+        ("Driver's licenses", DataClass::DriversLicenses),
+        ("Education levels", DataClass::EducationLevels),
+        ("Email addresses", DataClass::EmailAddresses),
+        ("Email messages", DataClass::EmailMessages),
+        ("Employers", DataClass::Employers),
+        ("Encrypted keys", DataClass::EncryptedKeys),
+        ("Ethnicities", DataClass::Ethnicities),
+        ("Genders", DataClass::Genders),
+        ("Geographic locations", DataClass::GeographicLocations),
+        ("Government issued IDs", DataClass::GovernmentIssuedIds),
+        ("Historical passwords", DataClass::HistoricalPasswords),
+        ("Income levels", DataClass::IncomeLevels),
+        (
+            "Instant messenger identities",
+            DataClass::InstantMessengerIdentities,
+        ),
+        ("IP addresses", DataClass::IpAddresses),
+        ("Job titles", DataClass::JobTitles),
+        ("Marital statuses", DataClass::MaritalStatuses),
+        ("Names", DataClass::Names),
+        ("Nationalities", DataClass::Nationalities),
+        ("Nicknames", DataClass::Nicknames),
+        ("Occupations", DataClass::Occupations),
+        ("Partial credit card data", DataClass::PartialCreditCardData),
+        ("Passport numbers", DataClass::PassportNumbers),
+        ("Password hints", DataClass::PasswordHints),
+        ("Passwords", DataClass::Passwords),
+        ("Payment histories", DataClass::PaymentHistories),
+        ("Personal health data", DataClass::PersonalHealthData),
+        ("Phone numbers", DataClass::PhoneNumbers),
+        ("Physical addresses", DataClass::PhysicalAddresses),
+        ("PINs", DataClass::Pins),
+        ("Political views", DataClass::PoliticalViews),
+        ("Private messages", DataClass::PrivateMessages),
+        ("Profile photos", DataClass::ProfilePhotos),
+        ("Purchases", DataClass::Purchases),
+        (
+            "Recovery email addresses",
+            DataClass::RecoveryEmailAddresses,
+        ),
+        ("Religions", DataClass::Religions),
+        (
+            "Security questions and answers",
+            DataClass::SecurityQuestionsAndAnswers,
+        ),
+        ("Sexual orientations", DataClass::SexualOrientations),
+        ("SMS messages", DataClass::SmsMessages),
+        ("Social media profiles", DataClass::SocialMediaProfiles),
+        ("Social security numbers", DataClass::SocialSecurityNumbers),
+        ("Spoken languages", DataClass::SpokenLanguages),
+        ("Time zones", DataClass::TimeZones),
+        ("Usernames", DataClass::Usernames),
+        ("Website activity", DataClass::WebsiteActivity),
+    ];
+    assert_eq!(expected.len(), DataClass::KNOWN.len());
+
+    for (name, class) in expected.iter() {
+        assert_eq!(name.parse::<DataClass>().unwrap(), *class);
+        assert_eq!(class.to_string(), *name);
+    }
+}
+
+#[test]
+fn test_data_class_other() {
+    let class: DataClass = "Astrological signs".parse().unwrap();
+    assert_eq!(class, DataClass::Other("Astrological signs".to_string()));
+    assert_eq!(class.to_string(), "Astrological signs");
+
+    // Names are matched exactly, like HIBP returns them
+    assert_eq!(
+        "email addresses".parse::<DataClass>().unwrap(),
+        DataClass::Other("email addresses".to_string())
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_data_class_round_trip() {
+    let classes = vec![
+        DataClass::EmailAddresses,
+        DataClass::IpAddresses,
+        DataClass::Other("Astrological signs".to_string()),
+    ];
+    let json = serde_json::to_string(&classes).unwrap();
+    assert_eq!(
+        json,
+        r#"["Email addresses","IP addresses","Astrological signs"]"#
+    );
+    assert_eq!(
+        serde_json::from_str::<Vec<DataClass>>(&json).unwrap(),
+        classes
+    );
+}
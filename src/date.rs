@@ -0,0 +1,297 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A calendar date, as used by HIBP for e.g. `BreachInfo::breach_date`.
+///
+/// Dates are ordered chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct BreachDate {
+    year: u32,
+    month: u8,
+    day: u8,
+}
+
+impl BreachDate {
+    /// Parse a date in `YYYY-MM-DD` format.
+    /// Returns `None` if `s` is not in that format or is not a valid date.
+    pub fn parse(s: &str) -> Option<Self> {
+        let bytes = s.as_bytes();
+        if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+            return None;
+        }
+        if !bytes
+            .iter()
+            .enumerate()
+            .all(|(idx, b)| idx == 4 || idx == 7 || b.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let year: u32 = s[..4].parse().ok()?;
+        let month: u8 = s[5..7].parse().ok()?;
+        let day: u8 = s[8..].parse().ok()?;
+        if month == 0 || month > 12 || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        Some(Self { year, month, day })
+    }
+
+    /// Parse the date of an ISO 8601 date and time, e.g. `2013-12-04T00:00Z`, ignoring the time.
+    /// A plain `YYYY-MM-DD` date is accepted as well.
+    #[cfg(any(test, feature = "serde"))]
+    pub(crate) fn parse_date_time(s: &str) -> Option<Self> {
+        match s.split_once('T') {
+            Some((date, _)) => Self::parse(date),
+            None => Self::parse(s),
+        }
+    }
+
+    /// The year of the date.
+    pub fn year(&self) -> u32 {
+        self.year
+    }
+
+    /// The month of the date, from 1 to 12.
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// The day of the month of the date, from 1 to 31.
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Number of whole days between the date and today, in UTC.
+    /// Returns 0 if the date is today or in the future.
+    pub fn days_ago(&self) -> u64 {
+        let today = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since_epoch| since_epoch.as_secs() / 86400);
+
+        self.days_ago_from(today)
+    }
+
+    /// Whether the date is at most `days` days ago. Dates in the future are always within.
+    pub fn is_within_last_days(&self, days: u64) -> bool {
+        self.days_ago() <= days
+    }
+
+    fn days_ago_from(&self, today: u64) -> u64 {
+        today.saturating_sub(self.days_since_epoch())
+    }
+
+    /// Number of days between 1970-01-01 and the date, or 0 for dates before it.
+    fn days_since_epoch(&self) -> u64 {
+        let leap_days = |year: u32| year / 4 - year / 100 + year / 400;
+        let prior_years = self.year.saturating_sub(1);
+        // Days from 0001-01-01 to January 1st of the year
+        let year_days = u64::from(prior_years) * 365 + u64::from(leap_days(prior_years));
+        let month_days: u64 = (1..self.month)
+            .map(|month| u64::from(days_in_month(self.year, month)))
+            .sum();
+        // 1970-01-01 is 719162 days after 0001-01-01
+        (year_days + month_days + u64::from(self.day) - 1).saturating_sub(719_162)
+    }
+}
+
+impl fmt::Display for BreachDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+fn days_in_month(year: u32, month: u8) -> u8 {
+    match month {
+        2 if year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400)) => {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for BreachDate {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for BreachDate {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let date = String::deserialize(deserializer)?;
+        Self::parse(&date).ok_or_else(|| {
+            serde::de::Error::invalid_value(serde::de::Unexpected::Str(&date), &"a YYYY-MM-DD date")
+        })
+    }
+}
+
+/// Deserialize an optional ISO 8601 date, or date and time, to `None` if it is missing, null or
+/// cannot be parsed, so that a single odd date in a HIBP response doesn't fail the whole response.
+#[cfg(feature = "serde")]
+pub(crate) fn deserialize_lenient<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<BreachDate>, D::Error> {
+    let date: Option<String> = serde::Deserialize::deserialize(deserializer)?;
+    Ok(date.as_deref().and_then(BreachDate::parse_date_time))
+}
+
+#[cfg(test)]
+fn date(year: u32, month: u8, day: u8) -> BreachDate {
+    BreachDate { year, month, day }
+}
+
+#[test]
+fn test_parse_valid_dates() {
+    assert_eq!(BreachDate::parse("2013-10-04"), Some(date(2013, 10, 4)));
+    assert_eq!(BreachDate::parse("0001-01-01"), Some(date(1, 1, 1)));
+    assert_eq!(BreachDate::parse("9999-12-31"), Some(date(9999, 12, 31)));
+    // Leap years
+    assert_eq!(BreachDate::parse("2020-02-29"), Some(date(2020, 2, 29)));
+    assert_eq!(BreachDate::parse("2000-02-29"), Some(date(2000, 2, 29)));
+    assert_eq!(BreachDate::parse("2019-04-30"), Some(date(2019, 4, 30)));
+
+    let parsed = BreachDate::parse("2013-10-04").unwrap();
+    assert_eq!(parsed.year(), 2013);
+    assert_eq!(parsed.month(), 10);
+    assert_eq!(parsed.day(), 4);
+}
+
+#[test]
+fn test_parse_invalid_dates() {
+    for invalid in [
+        "",
+        "2013",
+        "2013-10",
+        "2013-10-4",
+        "2013-1-04",
+        "13-10-04",
+        "2013-10-04 ",
+        " 2013-10-04",
+        "2013-10-04T00:00Z",
+        "2013/10/04",
+        "04-10-2013",
+        "2013-10-0a",
+        "+013-10-04",
+        "2013-+1-04",
+        "2013-10--4",
+        "２013-10-04",
+        "2013-00-04",
+        "2013-13-04",
+        "2013-10-00",
+        "2013-10-32",
+        "2013-04-31",
+        "2019-02-29",
+        "1900-02-29",
+    ]
+    .iter()
+    {
+        assert_eq!(BreachDate::parse(invalid), None, "{:?}", invalid);
+    }
+}
+
+#[test]
+fn test_parse_date_time() {
+    assert_eq!(
+        BreachDate::parse_date_time("2013-12-04T00:00Z"),
+        Some(date(2013, 12, 4))
+    );
+    assert_eq!(
+        BreachDate::parse_date_time("2022-05-15T23:52:49Z"),
+        Some(date(2022, 5, 15))
+    );
+    assert_eq!(
+        BreachDate::parse_date_time("2013-12-04"),
+        Some(date(2013, 12, 4))
+    );
+    assert_eq!(BreachDate::parse_date_time("04/12/2013T00:00Z"), None);
+    assert_eq!(BreachDate::parse_date_time(""), None);
+}
+
+#[test]
+fn test_breach_date_ordering_and_display() {
+    let mut dates = vec![
+        date(2014, 1, 1),
+        date(2013, 12, 31),
+        date(2013, 2, 10),
+        date(2013, 10, 4),
+    ];
+    dates.sort();
+    assert_eq!(
+        dates,
+        vec![
+            date(2013, 2, 10),
+            date(2013, 10, 4),
+            date(2013, 12, 31),
+            date(2014, 1, 1)
+        ]
+    );
+
+    assert_eq!(date(2013, 2, 1).to_string(), "2013-02-01");
+    assert_eq!(date(1, 1, 1).to_string(), "0001-01-01");
+    assert_eq!(
+        BreachDate::parse(&date(2020, 2, 29).to_string()),
+        Some(date(2020, 2, 29))
+    );
+}
+
+#[test]
+fn test_days_ago() {
+    assert_eq!(date(1970, 1, 1).days_since_epoch(), 0);
+    assert_eq!(date(1970, 1, 2).days_since_epoch(), 1);
+    assert_eq!(date(1971, 1, 1).days_since_epoch(), 365);
+    assert_eq!(date(2000, 3, 1).days_since_epoch(), 11017);
+    assert_eq!(date(2013, 10, 4).days_since_epoch(), 15982);
+    assert_eq!(date(1969, 12, 31).days_since_epoch(), 0);
+
+    let today = date(2013, 10, 4).days_since_epoch();
+    assert_eq!(date(2013, 10, 4).days_ago_from(today), 0);
+    assert_eq!(date(2013, 10, 3).days_ago_from(today), 1);
+    assert_eq!(date(2012, 10, 4).days_ago_from(today), 365);
+    // Dates in the future are 0 days ago
+    assert_eq!(date(2013, 10, 5).days_ago_from(today), 0);
+
+    // Against the system clock
+    assert!(date(2013, 10, 4).days_ago() > 4000);
+    assert!(date(2013, 10, 4).is_within_last_days(u64::MAX));
+    assert!(!date(2013, 10, 4).is_within_last_days(30));
+    assert!(date(9999, 12, 31).is_within_last_days(0));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_breach_date_serde() {
+    let date_json = serde_json::to_string(&date(2013, 10, 4)).unwrap();
+    assert_eq!(date_json, r#""2013-10-04""#);
+    assert_eq!(
+        serde_json::from_str::<BreachDate>(&date_json).unwrap(),
+        date(2013, 10, 4)
+    );
+    assert!(serde_json::from_str::<BreachDate>(r#""2013-10-04T00:00Z""#).is_err());
+    assert!(serde_json::from_str::<BreachDate>(r#""2013-02-30""#).is_err());
+}
@@ -0,0 +1,152 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::api;
+use crate::errors::CheckpwnError;
+use zeroize::Zeroizing;
+
+/// `Email` is a wrapper type for an email address that is checked at HIBP. Unlike `Account`, it
+/// only accepts strings that look like an email address. The email address is zeroized when
+/// dropped, and it contains an opaque `Debug` impl to avoid leaking it.
+#[derive(Clone)]
+pub struct Email(Zeroizing<String>);
+
+impl Email {
+    /// Make a new `Email`. Returns `Err(CheckpwnError::EmptyInput)` if `email` is empty, and
+    /// `Err(CheckpwnError::BadResponse)` if it doesn't contain exactly one `@` with a local part
+    /// before it and a domain after it. The domain is normalized with `normalize_account_email`.
+    pub fn new(email: &str) -> Result<Self, CheckpwnError> {
+        if email.is_empty() {
+            return Err(CheckpwnError::EmptyInput);
+        }
+        if !api::validate_account_string(email) {
+            return Err(CheckpwnError::BadResponse);
+        }
+
+        Ok(Self(Zeroizing::new(normalize_account_email(email)?)))
+    }
+
+    /// The email address percent-encoded for use in HIBP API URLs, e.g. `user%2Btag%40example.com`.
+    pub fn api_encoded(&self) -> String {
+        api::encode_account(&self.0)
+    }
+
+    pub(crate) fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+/// Convert the domain of `email` to its ASCII form, with any non-ASCII labels encoded as punycode,
+/// e.g. `user@münchen.de` becomes `user@xn--mnchen-3ya.de`. The local part is left unchanged.
+/// Err() is returned if `email` has no `@` or its domain is not a valid internationalized domain name.
+pub fn normalize_account_email(email: &str) -> Result<String, CheckpwnError> {
+    let (local, domain) = email.rsplit_once('@').ok_or(CheckpwnError::BadResponse)?;
+    let domain = idna::domain_to_ascii(domain).map_err(|_| CheckpwnError::BadResponse)?;
+
+    Ok(format!("{}@{}", local, domain))
+}
+
+impl std::fmt::Debug for Email {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Email(***REDACTED***)")
+    }
+}
+
+#[test]
+fn test_email_new() {
+    assert_eq!(Email::new("").unwrap_err(), CheckpwnError::EmptyInput);
+    for invalid in [
+        "test",
+        "@example.com",
+        "test@",
+        "test@@example.com",
+        "test@exa@mple.com",
+        "test @example.com",
+    ]
+    .iter()
+    {
+        assert_eq!(
+            Email::new(invalid).unwrap_err(),
+            CheckpwnError::BadResponse,
+            "{}",
+            invalid
+        );
+    }
+
+    assert_eq!(
+        Email::new("test@example.com").unwrap().as_str(),
+        "test@example.com"
+    );
+}
+
+#[test]
+fn test_email_api_encoded() {
+    assert_eq!(
+        Email::new("test@example.com").unwrap().api_encoded(),
+        "test%40example.com"
+    );
+    assert_eq!(
+        Email::new("user+alias@company.co.uk")
+            .unwrap()
+            .api_encoded(),
+        "user%2Balias%40company.co.uk"
+    );
+}
+
+#[test]
+fn test_normalize_account_email() {
+    // ASCII-only
+    assert_eq!(
+        normalize_account_email("test@example.com").unwrap(),
+        "test@example.com"
+    );
+    // Unicode local part and domain
+    assert_eq!(
+        normalize_account_email("jürgen@münchen.de").unwrap(),
+        "jürgen@xn--mnchen-3ya.de"
+    );
+    // ASCII local part and Unicode domain
+    assert_eq!(
+        normalize_account_email("user@bücher.example.com").unwrap(),
+        "user@xn--bcher-kva.example.com"
+    );
+
+    assert_eq!(
+        normalize_account_email("example.com").unwrap_err(),
+        CheckpwnError::BadResponse
+    );
+    assert_eq!(
+        normalize_account_email("test@xn--a.com").unwrap_err(),
+        CheckpwnError::BadResponse
+    );
+
+    assert_eq!(
+        Email::new("user@münchen.de").unwrap().api_encoded(),
+        "user%40xn--mnchen-3ya.de"
+    );
+}
+
+#[test]
+fn test_email_debug() {
+    let email = Email::new("test@example.com").unwrap();
+    assert_eq!(format!("{:?}", email), "Email(***REDACTED***)");
+}
@@ -20,37 +20,137 @@
 // OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
 // SOFTWARE.
 
+use crate::api;
+use std::sync::Arc;
+use std::time::Duration;
+
 /// Errors related to checking passwords and accounts.
-#[derive(Clone, Copy, PartialEq)]
+#[derive(Clone)]
 pub enum CheckpwnError {
-    ///
-    StatusCode,
-    ///
+    /// An unrecognized status code was received. Contains the status code.
+    UnexpectedStatus(u16),
+    /// The request could not be sent to HIBP.
     Network,
-    ///
+    /// The request could not be sent to HIBP. Contains the underlying error, which is also
+    /// returned by `Error::source`.
+    NetworkError(Arc<dyn std::error::Error + Send + Sync + 'static>),
+    /// The response body from HIBP could not be decoded.
     Decoding,
-    ///
+    /// HIBP returned a bad response, e.g. for an invalid account.
     BadResponse,
-    ///
+    /// The response body from HIBP could not be parsed.
+    MalformedResponse,
+    /// HIBP rejected the API key.
     InvalidApiKey,
-    ///
+    /// No API key was provided.
     MissingApiKey,
-    ///
+    /// The input was empty.
     EmptyInput,
+    /// The input was not a valid SHA1 hex digest.
+    InvalidHash,
+    /// The input was larger than the maximum size allowed for it, e.g. a password longer than
+    /// `MAX_PASSWORD_LEN`.
+    InputTooLarge,
+    /// Reading the input failed.
+    Io,
+    /// The request did not complete in time.
+    Timeout,
+    /// HIBP is temporarily unavailable, e.g. with status code 503.
+    ServiceUnavailable,
+    /// The requested resource, e.g. a breach, does not exist at HIBP.
+    NotFound,
+    /// HIBP rate limited the request. Contains how long to wait before retrying.
+    RateLimited(Duration),
+    /// The client configuration is invalid, e.g. a malformed proxy URL given to the builder.
+    /// Invalid arguments to a single check are reported with their own variants instead, e.g.
+    /// `InvalidDate`.
+    ConfigurationError,
+    /// The input was not a valid date in `YYYY-MM-DD` format.
+    InvalidDate,
+}
+
+impl CheckpwnError {
+    /// Wrap the error with a message describing what was being done when it occurred.
+    pub fn context(self, msg: &'static str) -> ContextualCheckpwnError {
+        ContextualCheckpwnError {
+            source: self,
+            context: msg,
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl CheckpwnError {
+    /// Name of the variant, e.g. "InvalidApiKey".
+    fn name(&self) -> &'static str {
+        match *self {
+            CheckpwnError::UnexpectedStatus(_) => "UnexpectedStatus",
+            CheckpwnError::Network => "Network",
+            CheckpwnError::NetworkError(_) => "NetworkError",
+            CheckpwnError::Decoding => "Decoding",
+            CheckpwnError::BadResponse => "BadResponse",
+            CheckpwnError::MalformedResponse => "MalformedResponse",
+            CheckpwnError::InvalidApiKey => "InvalidApiKey",
+            CheckpwnError::MissingApiKey => "MissingApiKey",
+            CheckpwnError::EmptyInput => "EmptyInput",
+            CheckpwnError::InvalidHash => "InvalidHash",
+            CheckpwnError::InputTooLarge => "InputTooLarge",
+            CheckpwnError::Io => "Io",
+            CheckpwnError::Timeout => "Timeout",
+            CheckpwnError::ServiceUnavailable => "ServiceUnavailable",
+            CheckpwnError::NotFound => "NotFound",
+            CheckpwnError::RateLimited(_) => "RateLimited",
+            CheckpwnError::ConfigurationError => "ConfigurationError",
+            CheckpwnError::InvalidDate => "InvalidDate",
+        }
+    }
+}
+
+/// Serialized as an object naming the variant, e.g. `{ "error": "InvalidApiKey" }`.
+/// `UnexpectedStatus` adds the status code as `"status"`, and `RateLimited` adds the
+/// number of seconds to wait as `"retry_after_secs"`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for CheckpwnError {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("error", self.name())?;
+        match *self {
+            CheckpwnError::UnexpectedStatus(status) => map.serialize_entry("status", &status)?,
+            CheckpwnError::RateLimited(retry_after) => {
+                map.serialize_entry("retry_after_secs", &retry_after.as_secs())?
+            }
+            _ => (),
+        }
+        map.end()
+    }
 }
 
 impl AsRef<str> for CheckpwnError {
     fn as_ref(&self) -> &str {
         match *self {
-            CheckpwnError::StatusCode => "Unrecognized status code received",
-            CheckpwnError::Network => "Failed to send request to HIBP",
+            CheckpwnError::UnexpectedStatus(_) => "Unrecognized status code received",
+            CheckpwnError::Network | CheckpwnError::NetworkError(_) => {
+                "Failed to send request to HIBP"
+            }
             CheckpwnError::Decoding => "Failed to decode response from HIBP",
             CheckpwnError::BadResponse => {
                 "Received a bad response from HIBP - make sure the account is valid"
             }
+            CheckpwnError::MalformedResponse => "Received a malformed response from HIBP",
             CheckpwnError::InvalidApiKey => "HIBP deemed the current API key invalid",
             CheckpwnError::MissingApiKey => "The API key is missing",
             CheckpwnError::EmptyInput => "Empty input that should NOT be empty",
+            CheckpwnError::InvalidHash => "Input is not a valid SHA1 hex digest",
+            CheckpwnError::InputTooLarge => "Input is larger than the maximum size allowed",
+            CheckpwnError::Io => "Failed to read input",
+            CheckpwnError::Timeout => "The request to HIBP timed out",
+            CheckpwnError::ServiceUnavailable => "HIBP is temporarily unavailable",
+            CheckpwnError::NotFound => "The requested resource was not found at HIBP",
+            CheckpwnError::RateLimited(_) => "HIBP rate limited the request",
+            CheckpwnError::ConfigurationError => "The client configuration is invalid",
+            CheckpwnError::InvalidDate => "Input is not a valid YYYY-MM-DD date",
         }
     }
 }
@@ -67,8 +167,289 @@ impl std::fmt::Debug for CheckpwnError {
     }
 }
 
+/// `NetworkError`s are compared by the message of their underlying error, as the errors
+/// themselves can't be compared.
+impl PartialEq for CheckpwnError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (CheckpwnError::UnexpectedStatus(a), CheckpwnError::UnexpectedStatus(b)) => a == b,
+            (CheckpwnError::RateLimited(a), CheckpwnError::RateLimited(b)) => a == b,
+            (CheckpwnError::NetworkError(a), CheckpwnError::NetworkError(b)) => {
+                a.to_string() == b.to_string()
+            }
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
 impl std::error::Error for CheckpwnError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        None
+        match self {
+            CheckpwnError::NetworkError(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Maps status codes to the matching variant, e.g. 401 to `InvalidApiKey`, or else to
+/// `UnexpectedStatus`. Transport errors map to `Timeout`, or else to `NetworkError`.
+impl From<ureq::Error> for CheckpwnError {
+    fn from(err: ureq::Error) -> Self {
+        match err {
+            ureq::Error::Status(400, _) => CheckpwnError::BadResponse,
+            ureq::Error::Status(401, _) => CheckpwnError::InvalidApiKey,
+            ureq::Error::Status(404, _) => CheckpwnError::NotFound,
+            ureq::Error::Status(429, resp) => {
+                CheckpwnError::RateLimited(api::parse_retry_after(resp.header("Retry-After")))
+            }
+            ureq::Error::Status(503, _) => CheckpwnError::ServiceUnavailable,
+            ureq::Error::Status(code, _) => CheckpwnError::UnexpectedStatus(code),
+            ureq::Error::Transport(transport) if api::is_timeout(&transport) => {
+                CheckpwnError::Timeout
+            }
+            ureq::Error::Transport(transport) => CheckpwnError::NetworkError(Arc::new(transport)),
+        }
+    }
+}
+
+/// A number in a response from HIBP could not be parsed, e.g. the count of a range entry.
+impl From<std::num::ParseIntError> for CheckpwnError {
+    fn from(_: std::num::ParseIntError) -> Self {
+        CheckpwnError::MalformedResponse
+    }
+}
+
+/// Wraps the `CheckpwnError` in an `io::Error` of the closest matching kind, e.g.
+/// `io::ErrorKind::TimedOut` for `Timeout`, to allow using `?` in functions returning `io::Result`.
+/// The original error can be recovered with `io::Error::get_ref` and `downcast_ref`.
+impl From<CheckpwnError> for std::io::Error {
+    fn from(err: CheckpwnError) -> Self {
+        use std::io::ErrorKind;
+
+        let kind = match &err {
+            CheckpwnError::Network | CheckpwnError::NetworkError(_) => ErrorKind::ConnectionRefused,
+            CheckpwnError::Timeout => ErrorKind::TimedOut,
+            CheckpwnError::MalformedResponse | CheckpwnError::Decoding => ErrorKind::InvalidData,
+            CheckpwnError::EmptyInput
+            | CheckpwnError::InvalidHash
+            | CheckpwnError::InputTooLarge
+            | CheckpwnError::ConfigurationError
+            | CheckpwnError::InvalidDate => ErrorKind::InvalidInput,
+            CheckpwnError::InvalidApiKey | CheckpwnError::MissingApiKey => {
+                ErrorKind::PermissionDenied
+            }
+            CheckpwnError::NotFound => ErrorKind::NotFound,
+            _ => ErrorKind::Other,
+        };
+
+        std::io::Error::new(kind, err)
     }
 }
+
+/// A `CheckpwnError` annotated with context, created with `CheckpwnError::context`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ContextualCheckpwnError {
+    /// The original error.
+    pub source: CheckpwnError,
+    /// What was being done when the error occurred.
+    pub context: &'static str,
+}
+
+impl std::fmt::Display for ContextualCheckpwnError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.context, self.source)
+    }
+}
+
+impl std::error::Error for ContextualCheckpwnError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.source)
+    }
+}
+
+#[test]
+fn test_error_context() {
+    use std::error::Error;
+
+    let err = CheckpwnError::Network.context("checking admin password");
+    assert_eq!(err.source, CheckpwnError::Network);
+    assert_eq!(err.context, "checking admin password");
+    assert_eq!(
+        err.to_string(),
+        "checking admin password: Failed to send request to HIBP"
+    );
+    assert_eq!(
+        err.source().unwrap().to_string(),
+        CheckpwnError::Network.to_string()
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize_error() {
+    assert_eq!(
+        serde_json::to_string(&CheckpwnError::InvalidApiKey).unwrap(),
+        r#"{"error":"InvalidApiKey"}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&CheckpwnError::UnexpectedStatus(418)).unwrap(),
+        r#"{"error":"UnexpectedStatus","status":418}"#
+    );
+    assert_eq!(
+        serde_json::to_string(&CheckpwnError::RateLimited(Duration::from_secs(2))).unwrap(),
+        r#"{"error":"RateLimited","retry_after_secs":2}"#
+    );
+}
+
+#[test]
+fn test_error_messages() {
+    for (err, msg) in [
+        (
+            CheckpwnError::MalformedResponse,
+            "Received a malformed response from HIBP",
+        ),
+        (
+            CheckpwnError::InvalidHash,
+            "Input is not a valid SHA1 hex digest",
+        ),
+        (
+            CheckpwnError::NotFound,
+            "The requested resource was not found at HIBP",
+        ),
+        (
+            CheckpwnError::InputTooLarge,
+            "Input is larger than the maximum size allowed",
+        ),
+        (
+            CheckpwnError::ConfigurationError,
+            "The client configuration is invalid",
+        ),
+        (
+            CheckpwnError::InvalidDate,
+            "Input is not a valid YYYY-MM-DD date",
+        ),
+    ]
+    .iter()
+    {
+        assert_eq!(err.to_string(), *msg);
+        assert_eq!(format!("{:?}", err), *msg);
+    }
+}
+
+#[test]
+fn test_from_ureq_error() {
+    let status =
+        |code: u16, raw: &str| ureq::Error::Status(code, raw.parse::<ureq::Response>().unwrap());
+
+    for (code, expected) in [
+        (400, CheckpwnError::BadResponse),
+        (401, CheckpwnError::InvalidApiKey),
+        (404, CheckpwnError::NotFound),
+        (503, CheckpwnError::ServiceUnavailable),
+        (500, CheckpwnError::UnexpectedStatus(500)),
+        (418, CheckpwnError::UnexpectedStatus(418)),
+    ]
+    .iter()
+    {
+        let raw = format!("HTTP/1.1 {} Test\r\n\r\n", code);
+        assert_eq!(CheckpwnError::from(status(*code, &raw)), *expected);
+    }
+
+    assert_eq!(
+        CheckpwnError::from(status(
+            429,
+            "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 3\r\n\r\n"
+        )),
+        CheckpwnError::RateLimited(Duration::from_secs(3))
+    );
+    assert_eq!(
+        CheckpwnError::from(status(429, "HTTP/1.1 429 Too Many Requests\r\n\r\n")),
+        CheckpwnError::RateLimited(crate::DEFAULT_RATE_LIMIT_DELAY)
+    );
+
+    let io_err = |kind| ureq::Error::from(std::io::Error::new(kind, "test"));
+    assert_eq!(
+        CheckpwnError::from(io_err(std::io::ErrorKind::TimedOut)),
+        CheckpwnError::Timeout
+    );
+    assert_eq!(
+        CheckpwnError::from(io_err(std::io::ErrorKind::WouldBlock)),
+        CheckpwnError::Timeout
+    );
+
+    let err = CheckpwnError::from(io_err(std::io::ErrorKind::ConnectionRefused));
+    assert!(matches!(err, CheckpwnError::NetworkError(_)));
+    assert_eq!(err.to_string(), "Failed to send request to HIBP");
+}
+
+#[test]
+fn test_network_error_source() {
+    use std::error::Error;
+
+    let io_err = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset");
+    let err = CheckpwnError::from(ureq::Error::from(io_err));
+
+    // The chain leads down to the original IO error
+    let mut source = err.source();
+    let mut found = false;
+    while let Some(cause) = source {
+        if let Some(io_err) = cause.downcast_ref::<std::io::Error>() {
+            assert_eq!(io_err.kind(), std::io::ErrorKind::ConnectionReset);
+            found = true;
+        }
+        source = cause.source();
+    }
+    assert!(found);
+
+    assert!(CheckpwnError::Network.source().is_none());
+    assert_eq!(err, err.clone());
+    assert_ne!(err, CheckpwnError::Network);
+    assert_ne!(
+        CheckpwnError::UnexpectedStatus(500),
+        CheckpwnError::UnexpectedStatus(502)
+    );
+}
+
+#[test]
+fn test_into_io_error() {
+    use std::io::ErrorKind;
+
+    for (err, kind) in [
+        (CheckpwnError::Network, ErrorKind::ConnectionRefused),
+        (CheckpwnError::Timeout, ErrorKind::TimedOut),
+        (CheckpwnError::MalformedResponse, ErrorKind::InvalidData),
+        (CheckpwnError::EmptyInput, ErrorKind::InvalidInput),
+        (CheckpwnError::InvalidHash, ErrorKind::InvalidInput),
+        (CheckpwnError::InvalidApiKey, ErrorKind::PermissionDenied),
+        (CheckpwnError::MissingApiKey, ErrorKind::PermissionDenied),
+        (CheckpwnError::NotFound, ErrorKind::NotFound),
+        (CheckpwnError::UnexpectedStatus(500), ErrorKind::Other),
+    ]
+    .iter()
+    {
+        let io_err = std::io::Error::from(err.clone());
+        assert_eq!(io_err.kind(), *kind);
+        assert_eq!(io_err.to_string(), err.to_string());
+        assert_eq!(
+            io_err.get_ref().unwrap().downcast_ref::<CheckpwnError>(),
+            Some(err)
+        );
+    }
+
+    fn check() -> std::io::Result<()> {
+        Err(CheckpwnError::Timeout)?
+    }
+    assert_eq!(check().unwrap_err().kind(), ErrorKind::TimedOut);
+}
+
+#[test]
+fn test_from_parse_int_error() {
+    fn parse(count: &str) -> Result<u64, CheckpwnError> {
+        Ok(count.parse::<u64>()?)
+    }
+
+    assert_eq!(parse("3752262"), Ok(3752262));
+    assert_eq!(parse("five"), Err(CheckpwnError::MalformedResponse));
+    assert_eq!(parse(""), Err(CheckpwnError::MalformedResponse));
+    assert_eq!(parse("-1"), Err(CheckpwnError::MalformedResponse));
+}
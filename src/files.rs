@@ -0,0 +1,458 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::CheckpwnError;
+use crate::{check_account, check_password, ApiKey, Password};
+use std::fs::File;
+use std::io::{BufRead, BufReader, Lines};
+use std::path::Path;
+
+/// Result of checking a single account read from a file with `check_accounts_file`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccountFileResult {
+    /// Line of the file the account was read from, starting at 1.
+    pub line_number: u32,
+    /// The account that was checked.
+    pub account: String,
+    /// Whether the account is breached. Always `false` if `error` is `Some`.
+    pub breached: bool,
+    /// The error that occurred while reading or checking the account, if any.
+    pub error: Option<CheckpwnError>,
+}
+
+impl AccountFileResult {
+    /// Account name used for results that report a failure to read the file.
+    pub const IO_ERROR_ACCOUNT: &'static str = "<io error>";
+
+    fn io_error(line_number: u32) -> Self {
+        Self {
+            line_number,
+            account: Self::IO_ERROR_ACCOUNT.to_string(),
+            breached: false,
+            error: Some(CheckpwnError::Io),
+        }
+    }
+}
+
+/// The lines of a file that pass `keep`, each with its line number, starting at 1.
+/// If the file cannot be opened, or a line cannot be read, `Err` is yielded with the number of
+/// that line, or 0 if the file could not be opened, and iteration ends.
+struct FileLines {
+    lines: Option<Lines<BufReader<File>>>,
+    keep: fn(&str) -> bool,
+    line_number: u32,
+    failed: bool,
+}
+
+impl FileLines {
+    fn open(path: &Path, keep: fn(&str) -> bool) -> Self {
+        Self {
+            lines: File::open(path)
+                .ok()
+                .map(|file| BufReader::new(file).lines()),
+            keep,
+            line_number: 0,
+            failed: false,
+        }
+    }
+}
+
+impl Iterator for FileLines {
+    type Item = Result<(u32, String), u32>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+
+        let lines = match self.lines.as_mut() {
+            Some(lines) => lines,
+            None => {
+                // The file could not be opened.
+                self.failed = true;
+                return Some(Err(0));
+            }
+        };
+
+        for line in lines {
+            self.line_number += 1;
+
+            match line {
+                Ok(line) if (self.keep)(&line) => return Some(Ok((self.line_number, line))),
+                Ok(_) => continue,
+                Err(_) => {
+                    self.failed = true;
+                    return Some(Err(self.line_number));
+                }
+            }
+        }
+
+        None
+    }
+}
+
+struct AccountFileIter<'a> {
+    lines: FileLines,
+    api_key: &'a str,
+}
+
+impl Iterator for AccountFileIter<'_> {
+    type Item = AccountFileResult;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (line_number, line) = match self.lines.next()? {
+            Ok(line) => line,
+            Err(line_number) => return Some(AccountFileResult::io_error(line_number)),
+        };
+
+        let account = line.trim();
+        let result = check_account(account, self.api_key);
+        Some(AccountFileResult {
+            line_number,
+            account: account.to_string(),
+            breached: result == Ok(true),
+            error: result.err(),
+        })
+    }
+}
+
+/// Check each account listed in the file at `path`, one account per line, using a given API key.
+/// Blank lines and lines starting with `#` are skipped.
+/// The file is read lazily: each call to `next()` reads the next account and checks it with
+/// `check_account`, so the rate-limit sleep applies for every account.
+/// If the file cannot be read, a result with `error` set to `CheckpwnError::Io` and `account` set
+/// to `AccountFileResult::IO_ERROR_ACCOUNT` is yielded, and iteration ends.
+pub fn check_accounts_file<'a>(
+    path: &Path,
+    api_key: &'a str,
+) -> impl Iterator<Item = AccountFileResult> + 'a {
+    AccountFileIter {
+        lines: FileLines::open(path, is_account_line),
+        api_key,
+    }
+}
+
+struct PasswordFileIter<F> {
+    lines: FileLines,
+    check: F,
+}
+
+impl<F> Iterator for PasswordFileIter<F>
+where
+    F: Fn(&Password) -> Result<bool, CheckpwnError>,
+{
+    type Item = Result<(String, bool), CheckpwnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match self.lines.next()? {
+            Ok((_, line)) => line,
+            Err(_) => return Some(Err(CheckpwnError::Io)),
+        };
+
+        // The line is not trimmed, as whitespace may be part of the password.
+        let result = Password::new(&line).and_then(|password| (self.check)(&password));
+        Some(result.map(|breached| (line, breached)))
+    }
+}
+
+fn passwords_file_with<F>(path: &Path, check: F) -> PasswordFileIter<F>
+where
+    F: Fn(&Password) -> Result<bool, CheckpwnError>,
+{
+    PasswordFileIter {
+        lines: FileLines::open(path, |line| !line.is_empty()),
+        check,
+    }
+}
+
+/// Check each password listed in the file at `path`, one password per line.
+/// Empty lines are skipped. Lines of only whitespace are checked, as whitespace may be part of
+/// a password.
+/// The file is read lazily: each call to `next()` reads the next password and checks it with
+/// `check_password`, so the whole file is never loaded into memory.
+/// Yields each line together with whether the password on it is breached, or the error that
+/// occurred while checking it, e.g. `Err(CheckpwnError::InputTooLarge)` for a line longer than
+/// `MAX_PASSWORD_LEN`. If the file cannot be read, `Err(CheckpwnError::Io)` is yielded,
+/// and iteration ends.
+pub fn check_passwords_from_file(
+    path: &Path,
+) -> impl Iterator<Item = Result<(String, bool), CheckpwnError>> {
+    passwords_file_with(path, check_password)
+}
+
+/// Whether `line` of an account file lists an account, i.e. is neither blank nor a comment.
+fn is_account_line(line: &str) -> bool {
+    let account = line.trim();
+    !account.is_empty() && !account.starts_with('#')
+}
+
+struct AccountProgressIter<F, P> {
+    lines: FileLines,
+    check: F,
+    progress: P,
+    completed: usize,
+    total: usize,
+}
+
+impl<F, P> Iterator for AccountProgressIter<F, P>
+where
+    F: Fn(&str) -> Result<bool, CheckpwnError>,
+    P: Fn(usize, usize),
+{
+    type Item = Result<(String, bool), CheckpwnError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let account = match self.lines.next()? {
+            Ok((_, line)) => line.trim().to_string(),
+            Err(_) => return Some(Err(CheckpwnError::Io)),
+        };
+
+        let result = (self.check)(&account).map(|breached| (account, breached));
+        self.completed += 1;
+        (self.progress)(self.completed, self.total);
+
+        Some(result)
+    }
+}
+
+fn accounts_file_with<F, P>(path: &Path, check: F, progress: P) -> AccountProgressIter<F, P>
+where
+    F: Fn(&str) -> Result<bool, CheckpwnError>,
+    P: Fn(usize, usize),
+{
+    // A first pass counts the accounts that can be read, without keeping them in memory.
+    let total = FileLines::open(path, is_account_line)
+        .take_while(Result::is_ok)
+        .count();
+
+    AccountProgressIter {
+        lines: FileLines::open(path, is_account_line),
+        total,
+        check,
+        progress,
+        completed: 0,
+    }
+}
+
+/// Check each account listed in the file at `path`, one account per line, using a given `ApiKey`.
+/// Blank lines and lines starting with `#` are skipped.
+/// The file is read twice: first to count the accounts, then lazily, where each call to `next()`
+/// reads the next account and checks it with `check_account`, so the rate-limit sleep applies
+/// for every account.
+/// After each check, `progress(completed, total)` is called with the number of accounts checked
+/// so far and the total number of accounts in the file.
+/// Yields each account together with whether it is breached, or the error that occurred while
+/// checking it. If the file, or a line of it, cannot be read, `Err(CheckpwnError::Io)` is
+/// yielded after the accounts read before it, and iteration ends.
+pub fn check_accounts_from_file<'a>(
+    path: &Path,
+    api_key: &'a ApiKey,
+    progress: impl Fn(usize, usize) + 'a,
+) -> impl Iterator<Item = Result<(String, bool), CheckpwnError>> + 'a {
+    accounts_file_with(
+        path,
+        move |account| check_account(account, api_key.as_str()),
+        progress,
+    )
+}
+
+#[cfg(test)]
+fn temp_file_with(name: &str, contents: &str) -> std::path::PathBuf {
+    let path = std::env::temp_dir().join(format!("checkpwn_lib_{}_{}", std::process::id(), name));
+    std::fs::write(&path, contents).unwrap();
+
+    path
+}
+
+#[test]
+fn test_file_lines() {
+    let path = std::env::temp_dir().join(format!(
+        "checkpwn_lib_{}_file_lines.txt",
+        std::process::id()
+    ));
+    // The fourth line is not valid UTF-8, so it can't be read
+    std::fs::write(&path, b"first\n\nsecond\n\xff\nthird\n").unwrap();
+
+    let lines: Vec<_> = FileLines::open(&path, |line| !line.is_empty()).collect();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        lines,
+        vec![
+            Ok((1, "first".to_string())),
+            Ok((3, "second".to_string())),
+            Err(4)
+        ]
+    );
+    assert_eq!(
+        FileLines::open(&path, |_| true).collect::<Vec<_>>(),
+        vec![Err(0)]
+    );
+}
+
+#[test]
+fn test_check_accounts_file() {
+    let path = temp_file_with(
+        "accounts.txt",
+        "# Accounts to audit\n\nfirst@example.com\n  \n# second@example.com\nthird\n",
+    );
+
+    // Empty API key fails before any request is sent or the thread sleeps.
+    let results: Vec<AccountFileResult> = check_accounts_file(&path, "").collect();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].line_number, 3);
+    assert_eq!(results[0].account, "first@example.com");
+    assert_eq!(results[1].line_number, 6);
+    assert_eq!(results[1].account, "third");
+    assert!(results
+        .iter()
+        .all(|res| !res.breached && res.error == Some(CheckpwnError::EmptyInput)));
+}
+
+#[test]
+fn test_check_accounts_file_missing() {
+    let path = std::env::temp_dir().join("checkpwn_lib_does_not_exist.txt");
+    let results: Vec<AccountFileResult> = check_accounts_file(&path, "Test").collect();
+
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0].account, AccountFileResult::IO_ERROR_ACCOUNT);
+    assert_eq!(results[0].error, Some(CheckpwnError::Io));
+}
+
+#[test]
+fn test_check_passwords_from_file() {
+    let path = temp_file_with(
+        "passwords.txt",
+        &format!(
+            "qwerty\n\n   \ndHRUKbDaKgIobOtX\r\nnot a fixture\n{}\npassword\n",
+            "a".repeat(crate::MAX_PASSWORD_LEN + 1)
+        ),
+    );
+
+    let results: Vec<_> = passwords_file_with(&path, |password| {
+        crate::testing::check_password_deterministic_test(password.hash_hex())
+    })
+    .collect();
+    std::fs::remove_file(&path).unwrap();
+
+    // The empty line is skipped, but the whitespace-only line is checked as a password
+    assert_eq!(
+        results,
+        vec![
+            Ok(("qwerty".to_string(), true)),
+            Err(CheckpwnError::Network),
+            Ok(("dHRUKbDaKgIobOtX".to_string(), false)),
+            Err(CheckpwnError::Network),
+            Err(CheckpwnError::InputTooLarge),
+            Ok(("password".to_string(), true)),
+        ]
+    );
+}
+
+#[test]
+fn test_check_passwords_from_file_missing() {
+    let path = std::env::temp_dir().join("checkpwn_lib_does_not_exist.txt");
+    let results: Vec<_> = check_passwords_from_file(&path).collect();
+
+    assert_eq!(results, vec![Err(CheckpwnError::Io)]);
+}
+
+#[test]
+fn test_check_accounts_from_file() {
+    use std::cell::RefCell;
+
+    let path = temp_file_with(
+        "accounts_progress.txt",
+        "# Exported from HR\nbreached@example.com\n\n  clean@example.com  \nunknown@example.com\n",
+    );
+    let progress = RefCell::new(Vec::new());
+
+    let results: Vec<_> = accounts_file_with(
+        &path,
+        |account| match account {
+            "breached@example.com" => Ok(true),
+            "clean@example.com" => Ok(false),
+            _ => Err(CheckpwnError::BadResponse),
+        },
+        |completed, total| progress.borrow_mut().push((completed, total)),
+    )
+    .collect();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(
+        results,
+        vec![
+            Ok(("breached@example.com".to_string(), true)),
+            Ok(("clean@example.com".to_string(), false)),
+            Err(CheckpwnError::BadResponse),
+        ]
+    );
+    assert_eq!(*progress.borrow(), vec![(1, 3), (2, 3), (3, 3)]);
+}
+
+#[test]
+fn test_check_accounts_from_file_unreadable_line() {
+    use std::cell::RefCell;
+
+    // The third line is not valid UTF-8, so it can't be read
+    let path = std::env::temp_dir().join(format!(
+        "checkpwn_lib_{}_accounts_unreadable.txt",
+        std::process::id()
+    ));
+    std::fs::write(
+        &path,
+        b"first@example.com\nsecond@example.com\n\xff\nthird@example.com\n",
+    )
+    .unwrap();
+    let progress = RefCell::new(Vec::new());
+
+    let results: Vec<_> = accounts_file_with(
+        &path,
+        |_| Ok(false),
+        |completed, total| progress.borrow_mut().push((completed, total)),
+    )
+    .collect();
+    std::fs::remove_file(&path).unwrap();
+
+    // The accounts read before the unreadable line are still checked
+    assert_eq!(
+        results,
+        vec![
+            Ok(("first@example.com".to_string(), false)),
+            Ok(("second@example.com".to_string(), false)),
+            Err(CheckpwnError::Io),
+        ]
+    );
+    assert_eq!(*progress.borrow(), vec![(1, 2), (2, 2)]);
+}
+
+#[test]
+fn test_check_accounts_from_file_missing() {
+    let path = std::env::temp_dir().join("checkpwn_lib_does_not_exist.txt");
+    let api_key = ApiKey::new("Test").unwrap();
+    let results: Vec<_> =
+        check_accounts_from_file(&path, &api_key, |_, _| panic!("no account is checked")).collect();
+
+    assert_eq!(results, vec![Err(CheckpwnError::Io)]);
+}
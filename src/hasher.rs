@@ -0,0 +1,84 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::{api, HashAlgorithm};
+
+/// A hash algorithm used to make a `Password` with `Password::new_with_hasher`.
+///
+/// Implement this to plug in another algorithm, or a test double that returns a
+/// pre-determined hash so that password checks can be tested offline.
+pub trait PasswordHasher: Send + Sync {
+    /// Hash `input` and return the digest as hex.
+    fn hash(&self, input: &[u8]) -> String;
+
+    /// The range API the hashes are checked against. Defaults to `HashAlgorithm::Sha1`.
+    fn algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Sha1
+    }
+}
+
+/// `PasswordHasher` computing SHA1 digests, which is what `Password::new` uses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Sha1Hasher;
+
+impl PasswordHasher for Sha1Hasher {
+    fn hash(&self, input: &[u8]) -> String {
+        api::hash_password_bytes(input)
+    }
+}
+
+/// `PasswordHasher` computing NTLM digests, i.e. the MD4 digest of the UTF-16LE encoding of
+/// the input. Input that is not valid UTF-8 is converted lossily before encoding.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NtlmHasher;
+
+impl PasswordHasher for NtlmHasher {
+    fn hash(&self, input: &[u8]) -> String {
+        api::hash_password_ntlm(&String::from_utf8_lossy(input))
+    }
+
+    fn algorithm(&self) -> HashAlgorithm {
+        HashAlgorithm::Ntlm
+    }
+}
+
+#[test]
+fn test_sha1_hasher() {
+    assert_eq!(
+        Sha1Hasher.hash(b"password"),
+        "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8"
+    );
+    assert_eq!(
+        Sha1Hasher.hash(b"qwerty"),
+        "B1B3773A05C0ED0176787A4F1574FF0075F7521E"
+    );
+    assert_eq!(Sha1Hasher.algorithm(), HashAlgorithm::Sha1);
+}
+
+#[test]
+fn test_ntlm_hasher() {
+    assert_eq!(
+        NtlmHasher.hash(b"password"),
+        "8846F7EAEE8FB117AD06BDD830B7586C"
+    );
+    assert_eq!(NtlmHasher.algorithm(), HashAlgorithm::Ntlm);
+}
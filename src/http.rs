@@ -0,0 +1,123 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::api;
+use crate::errors::CheckpwnError;
+use std::time::Duration;
+
+/// HTTP client used by `CheckpwnClient` to send requests to HIBP.
+/// Implement this to substitute the transport, e.g. with a test double.
+pub trait HttpClient: Send + Sync {
+    /// Send a GET request to `url` with `headers` set, and return the status code and body of
+    /// the response. A response with a non-2XX status code must be returned as `Ok`, not `Err`,
+    /// except for HTTP 429, which must be returned as `Err(CheckpwnError::RateLimited)` with the
+    /// duration given in the `Retry-After` header, e.g. parsed with `parse_retry_after`.
+    /// `CheckpwnClient` treats a 429 returned as `Ok` as `CheckpwnError::UnexpectedStatus(429)`,
+    /// which is not retried.
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<(u16, String), CheckpwnError>;
+
+    /// Like `get`, but the whole request should complete within `timeout`, if it is `Some`.
+    /// The default implementation ignores `timeout` and calls `get`.
+    fn get_with_timeout(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        timeout: Option<Duration>,
+    ) -> Result<(u16, String), CheckpwnError> {
+        let _ = timeout;
+        self.get(url, headers)
+    }
+
+    /// Like `get_with_timeout`, but also return the `ETag` header of the response, if it has one.
+    /// The default implementation calls `get_with_timeout` and never returns an `ETag`.
+    fn get_with_etag(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        timeout: Option<Duration>,
+    ) -> Result<(u16, String, Option<String>), CheckpwnError> {
+        let (status, body) = self.get_with_timeout(url, headers, timeout)?;
+        Ok((status, body, None))
+    }
+}
+
+/// The default `HttpClient`, sending requests with `ureq`.
+#[derive(Debug, Clone)]
+pub struct DefaultHttpClient {
+    agent: ureq::Agent,
+}
+
+impl DefaultHttpClient {
+    /// Make a new `DefaultHttpClient` that sends requests with `agent`.
+    pub fn from_agent(agent: ureq::Agent) -> Self {
+        Self { agent }
+    }
+}
+
+impl Default for DefaultHttpClient {
+    fn default() -> Self {
+        Self::from_agent(
+            ureq::AgentBuilder::new()
+                .timeout_connect(crate::DEFAULT_CONNECT_TIMEOUT)
+                .build(),
+        )
+    }
+}
+
+impl HttpClient for DefaultHttpClient {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<(u16, String), CheckpwnError> {
+        self.get_with_timeout(url, headers, None)
+    }
+
+    fn get_with_timeout(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        timeout: Option<Duration>,
+    ) -> Result<(u16, String), CheckpwnError> {
+        let (status, body, _) = self.get_with_etag(url, headers, timeout)?;
+        Ok((status, body))
+    }
+
+    fn get_with_etag(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        timeout: Option<Duration>,
+    ) -> Result<(u16, String, Option<String>), CheckpwnError> {
+        let mut request = self.agent.get(url);
+        for (name, value) in headers {
+            request = request.set(name, value);
+        }
+        if let Some(timeout) = timeout {
+            request = request.timeout(timeout);
+        }
+
+        let (status, response) = api::response_to_status_codes(request.call())?;
+        let etag = response.header("ETag").map(String::from);
+        let body = response
+            .into_string()
+            .map_err(|_| CheckpwnError::Decoding)?;
+
+        Ok((status, body, etag))
+    }
+}
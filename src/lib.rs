@@ -21,7 +21,10 @@
 // SOFTWARE.
 
 //! # Usage:
-//! ```rust
+//! Checking sends requests to HIBP, so this example is only compiled, not run, as a doc test.
+//! For tests that must not depend on the network, see the `testing` module, enabled with the
+//! `testing` feature, e.g. `testing::MockHttpClient`.
+//! ```rust,no_run
 //! use checkpwn_lib::{Password, check_password, check_account, CheckpwnError};
 //!
 //! let password = Password::new("qwerty")?;
@@ -41,71 +44,457 @@
     unused_qualifications,
     overflowing_literals
 )]
-#![doc(html_root_url = "https://docs.rs/checkpwn_lib/0.2.1")]
+#![doc(html_root_url = "https://docs.rs/checkpwn_lib/0.3.0")]
 
+/// Emit a debug event with `tracing`, if the `tracing` feature is enabled, or else a `log`
+/// record, if the `logging` feature is enabled. With both, `tracing` forwards events to `log`.
+/// Never pass API keys, passwords or hashes to this.
+macro_rules! debug_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+        #[cfg(all(feature = "logging", not(feature = "tracing")))]
+        log::debug!($($arg)*);
+    };
+}
+
+/// Like `debug_event`, but at the warn level.
+macro_rules! warn_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::warn!($($arg)*);
+        #[cfg(all(feature = "logging", not(feature = "tracing")))]
+        log::warn!($($arg)*);
+    };
+}
+
+mod account;
 mod api;
+mod api_key;
+#[cfg(any(feature = "proptest", feature = "quickcheck"))]
+mod arbitrary;
+#[cfg(feature = "tokio")]
+mod async_api;
+mod batch;
+mod breach;
+mod cache;
+mod client;
+mod common;
+mod config;
+mod data_class;
+mod date;
+mod email;
 mod errors;
+mod files;
+mod hasher;
+mod http;
+mod metrics;
+#[cfg(feature = "migration_tools")]
+mod migration;
+mod range;
+mod rate_limit;
+mod results;
+mod retry;
+mod telemetry;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
 
-pub use errors::CheckpwnError;
+pub use account::Account;
+pub use api::{
+    hash_password, password_prefix_to_range_url, HIBP_BASE_URL, HIBP_PASS_BASE_URL,
+    RANGE_PREFIX_LEN,
+};
+pub use api_key::ApiKey;
+#[cfg(feature = "tokio")]
+pub use async_api::{
+    check_account_async, check_password_async, check_password_stream, check_passwords_concurrent,
+};
+pub use batch::{
+    check_accounts_batch, check_passwords_batch, summarise_password_batch_results, BatchSummary,
+};
+pub use breach::{BreachFilter, BreachInfo, BreachList, PasteInfo, SubscriptionStatus};
+pub use cache::RangeCache;
+pub use client::{
+    CheckpwnClient, CheckpwnClientBuilder, DEFAULT_CONNECT_TIMEOUT, DEFAULT_RATE_LIMIT_DELAY,
+    DEFAULT_READ_TIMEOUT,
+};
+pub use common::{detect_common_password, CommonPasswordSet};
+pub use config::CheckpwnConfig;
+pub use data_class::DataClass;
+pub use date::BreachDate;
+pub use email::{normalize_account_email, Email};
+pub use errors::{CheckpwnError, ContextualCheckpwnError};
+pub use files::{
+    check_accounts_file, check_accounts_from_file, check_passwords_from_file, AccountFileResult,
+};
+pub use hasher::{NtlmHasher, PasswordHasher, Sha1Hasher};
+pub use http::{DefaultHttpClient, HttpClient};
+pub use metrics::{Metrics, NoopMetrics};
+#[cfg(feature = "migration_tools")]
+pub use migration::{diff_accounts, BreachDiff};
+pub use range::RangeResponse;
+pub use rate_limit::{FixedDelay, NoDelay, RateLimitStrategy, TokenBucket};
+pub use results::{AccountBreachResult, BreachResult};
+pub use retry::RetryConfig;
 use std::{thread, time};
+pub use telemetry::TelemetryCollector;
+
+/// Internals exposed to the fuzz targets in `fuzz/`, which are built with `--cfg fuzzing`.
+#[cfg(fuzzing)]
+#[doc(hidden)]
+pub mod fuzzing {
+    pub use crate::api::{arg_to_api_route, search_in_range, CheckableChoices};
+}
 
 /// The checkpwn UserAgent sent to HIBP.
 pub const CHECKPWN_USER_AGENT: &str = "checkpwn - cargo utility tool for hibp";
 
+/// Maximum size, in bytes, of a password given to `Password`. Larger passwords are rejected with
+/// `CheckpwnError::InputTooLarge`, instead of hashing arbitrarily large input, e.g. from a file
+/// that isn't a password list.
+pub const MAX_PASSWORD_LEN: usize = 1024;
+
 /// Check account, on both account and paste databases, using a given API key.
 /// Before sending a request, the thread sleeps for 1600 millis. HIBP limits at 1500.
 /// Returns Ok(bool), `bool` indicating whether the account is breached or not.
 /// Err() is returned if an error occurred during the check.
 pub fn check_account(account: &str, api_key: &str) -> Result<bool, CheckpwnError> {
-    if account.is_empty() || api_key.is_empty() {
+    check_account_with_sleep(account, api_key, thread::sleep)
+}
+
+/// Check account, on both account and paste databases, using a given API key.
+/// Before sending a request, `sleep_fn` is called with `DEFAULT_RATE_LIMIT_DELAY`, the rate limit
+/// delay of the default client, in place of sleeping the thread, e.g. with `|_| {}` to not sleep
+/// at all in tests.
+/// Returns Ok(bool), `bool` indicating whether the account is breached or not.
+/// Err() is returned if an error occurred during the check.
+pub fn check_account_with_sleep(
+    account: &str,
+    api_key: &str,
+    sleep_fn: impl Fn(time::Duration),
+) -> Result<bool, CheckpwnError> {
+    client::default_client().check_account_with_sleep(account, api_key, sleep_fn)
+}
+
+/// Check account, on both account and paste databases, using a given `ApiKey`.
+/// Like `check_account`, the thread sleeps for `DEFAULT_RATE_LIMIT_DELAY` before sending a request.
+/// Returns Ok(bool), `bool` indicating whether the account is breached or not.
+/// Err() is returned if an error occurred during the check.
+pub fn check_account_keyed(account: &str, api_key: &ApiKey) -> Result<bool, CheckpwnError> {
+    check_account(account, api_key.as_str())
+}
+
+/// Check account, on both account and paste databases, using a given `ApiKey`.
+/// Like `check_account`, the thread sleeps for `DEFAULT_RATE_LIMIT_DELAY` before sending a request.
+/// Returns Ok(bool), `bool` indicating whether the account is breached or not.
+/// Err() is returned if an error occurred during the check.
+pub fn check_account_typed(account: &Account, api_key: &ApiKey) -> Result<bool, CheckpwnError> {
+    check_account(account.as_str(), api_key.as_str())
+}
+
+/// Check an email address, on both account and paste databases, using a given `ApiKey`.
+/// Like `check_account`, the thread sleeps for `DEFAULT_RATE_LIMIT_DELAY` before sending a request.
+/// Returns Ok(bool), `bool` indicating whether the email address is breached or not.
+/// Err() is returned if an error occurred during the check.
+pub fn check_account_email(email: &Email, api_key: &ApiKey) -> Result<bool, CheckpwnError> {
+    check_account(email.as_str(), api_key.as_str())
+}
+
+/// Check account, on both account and paste databases, using a given API key.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Returns whether the account was found in each of the databases.
+/// Err() is returned if an error occurred during the check.
+pub fn check_account_detailed(
+    account: &str,
+    api_key: &str,
+) -> Result<AccountBreachResult, CheckpwnError> {
+    client::default_client().check_account_detailed(account, api_key)
+}
+
+/// Get the details of every breach `account` appears in, using a given API key.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Returns an empty list if the account is not breached.
+/// Err() is returned if an error occurred during the request.
+#[cfg(feature = "serde")]
+pub fn get_breach_details(account: &str, api_key: &str) -> Result<BreachList, CheckpwnError> {
+    client::default_client().get_breach_details(account, api_key)
+}
+
+/// Check whether `account` appears in any breach added to HIBP after `date_ymd`, given in
+/// `YYYY-MM-DD` format, using a given API key.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Returns Ok(bool), `bool` indicating whether the account is in a breach added after `date_ymd`.
+/// `Err(CheckpwnError::InvalidDate)` is returned if `date_ymd` is not a valid `YYYY-MM-DD` date.
+/// Err() is returned if an error occurred during the check.
+#[cfg(feature = "serde")]
+pub fn check_account_breached_since(
+    account: &str,
+    api_key: &str,
+    date_ymd: &str,
+) -> Result<bool, CheckpwnError> {
+    client::default_client().check_account_breached_since(account, api_key, date_ymd)
+}
+
+/// Get the details of every breach in the HIBP database, using a given `ApiKey`.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Err() is returned if an error occurred during the request.
+#[cfg(feature = "serde")]
+pub fn get_all_breaches(api_key: &ApiKey) -> Result<BreachList, CheckpwnError> {
+    client::default_client().get_all_breaches(api_key.as_str())
+}
+
+/// Get the details of the breach called `name`, using a given `ApiKey`.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// `Err(CheckpwnError::NotFound)` is returned if there is no breach called `name`.
+/// Err() is returned if another error occurred during the request.
+#[cfg(feature = "serde")]
+pub fn get_breach_by_name(name: &str, api_key: &ApiKey) -> Result<BreachInfo, CheckpwnError> {
+    client::default_client().get_breach_by_name(name, api_key.as_str())
+}
+
+/// Get every data class, e.g. "Email addresses", that HIBP uses to describe breached data,
+/// using a given `ApiKey`.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Err() is returned if an error occurred during the request.
+#[cfg(feature = "serde")]
+pub fn get_data_classes(api_key: &ApiKey) -> Result<Vec<String>, CheckpwnError> {
+    client::default_client().get_data_classes(api_key.as_str())
+}
+
+/// Get the details of the breach most recently added to HIBP, using a given `ApiKey`.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Err() is returned if an error occurred during the request.
+#[cfg(feature = "serde")]
+pub fn get_latest_breach(api_key: &ApiKey) -> Result<BreachInfo, CheckpwnError> {
+    client::default_client().get_latest_breach(api_key.as_str())
+}
+
+/// Get the status of the subscription a given `ApiKey` belongs to.
+/// Batch jobs can use `SubscriptionStatus::request_delay` to wait only as long as their
+/// subscription requires between requests, e.g. with `CheckpwnClientBuilder::rate_limit_delay`.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Err() is returned if an error occurred during the request.
+#[cfg(feature = "serde")]
+pub fn get_subscription_status(api_key: &ApiKey) -> Result<SubscriptionStatus, CheckpwnError> {
+    client::default_client().get_subscription_status(api_key.as_str())
+}
+
+/// Get every paste `account` appears in, using a given API key.
+/// Before sending a request, the thread waits as set by the rate limit of the default client,
+/// which sleeps for `DEFAULT_RATE_LIMIT_DELAY`.
+/// Returns an empty `Vec` if the account is not found in any pastes.
+/// Err() is returned if an error occurred during the request.
+#[cfg(feature = "serde")]
+pub fn get_pastes_for_account(
+    account: &str,
+    api_key: &str,
+) -> Result<Vec<PasteInfo>, CheckpwnError> {
+    client::default_client().get_pastes_for_account(account, api_key)
+}
+
+/// Check an email address, on both account and paste databases, using a given API key.
+/// Unlike `check_account`, which accepts both usernames and email addresses (as HIBP does),
+/// this only accepts strings that look like an email address and returns
+/// `Err(CheckpwnError::BadResponse)` without querying HIBP otherwise.
+/// Like `check_account`, the thread sleeps for `DEFAULT_RATE_LIMIT_DELAY` before sending a request.
+pub fn check_account_email_only(email: &str, api_key: &str) -> Result<bool, CheckpwnError> {
+    if email.is_empty() || api_key.is_empty() {
         return Err(CheckpwnError::EmptyInput);
     }
+    if !api::validate_account_string(email) {
+        return Err(CheckpwnError::BadResponse);
+    }
 
-    // HIBP limits requests to one per 1500 milliseconds. We're allowing for 1600 below as a buffer.
-    thread::sleep(time::Duration::from_millis(1600));
+    check_account(email, api_key)
+}
 
-    let acc_db_api_route = api::arg_to_api_route(&api::CheckableChoices::Acc, account);
-    let paste_db_api_route = api::arg_to_api_route(&api::CheckableChoices::Paste, account);
+/// Check several accounts, one after the other, using a given API key.
+/// Each account is checked with `check_account`, so the rate-limit sleep applies before every check.
+/// If `on_progress` is `Some`, it is called after each check with the zero-based index of the
+/// account just checked and the total number of accounts.
+/// Returns each account paired with its result, in the order given.
+pub fn check_accounts_sequential<F: Fn(usize, usize)>(
+    accounts: &[&str],
+    api_key: &str,
+    on_progress: Option<F>,
+) -> Vec<(String, Result<bool, CheckpwnError>)> {
+    let total = accounts.len();
+    let mut results = Vec::with_capacity(total);
 
-    let agent: ureq::Agent = ureq::AgentBuilder::new()
-        .timeout_connect(time::Duration::from_secs(10))
-        .build();
+    for (idx, account) in accounts.iter().enumerate() {
+        results.push((account.to_string(), check_account(account, api_key)));
 
-    let acc_stat = agent
-        .get(&acc_db_api_route)
-        .set("User-Agent", CHECKPWN_USER_AGENT)
-        .set("hibp-api-key", api_key)
-        .call();
+        if let Some(ref progress) = on_progress {
+            progress(idx, total);
+        }
+    }
 
-    let paste_stat = agent
-        .get(&paste_db_api_route)
-        .set("User-Agent", CHECKPWN_USER_AGENT)
-        .set("hibp-api-key", api_key)
-        .call();
+    results
+}
 
-    api::evaluate_acc_breach_statuscodes(
-        api::response_to_status_codes(&acc_stat)?,
-        api::response_to_status_codes(&paste_stat)?,
-    )
+/// The hash algorithm used to check a `Password` against HIBP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HashAlgorithm {
+    /// SHA1, the default used by HIBP.
+    #[default]
+    Sha1,
+    /// NTLM, as used by Windows and Active Directory password databases.
+    Ntlm,
 }
 
 /// `Password` is a wrapper type for a password that is checked at HIBP.
-/// It contains an opaque `Debug` impl, to avoid the SHA1 hash of the password to leak.
+/// It contains an opaque `Debug` impl, to avoid the hash of the password to leak.
 pub struct Password {
     hash: String,
+    algorithm: HashAlgorithm,
 }
 
 impl Password {
-    /// Hash and make a new `Password`. Returns `Err` if `password` is empty.
+    /// Hash and make a new `Password`. Returns `Err` if `password` is empty or longer than
+    /// `MAX_PASSWORD_LEN` bytes.
+    ///
+    /// The UTF-8 bytes of `password` are hashed as-is, so the same text in different Unicode
+    /// normalization forms gives different hashes. Use `Password::new_nfc` to normalize first.
     pub fn new(password: &str) -> Result<Self, CheckpwnError> {
-        if password.is_empty() {
-            return Err(CheckpwnError::EmptyInput);
+        Self::new_with_algorithm(password, HashAlgorithm::Sha1)
+    }
+
+    /// Normalize `password` to Unicode NFC, then hash and make a new `Password`.
+    /// Returns `Err` if `password` is empty or, once normalized, longer than `MAX_PASSWORD_LEN`
+    /// bytes.
+    ///
+    /// This makes e.g. a precomposed `é` and an `e` followed by a combining acute accent
+    /// give the same hash.
+    pub fn new_nfc(password: &str) -> Result<Self, CheckpwnError> {
+        use unicode_normalization::UnicodeNormalization;
+
+        let normalized = zeroize::Zeroizing::new(password.nfc().collect::<String>());
+        Self::new(&normalized)
+    }
+
+    /// Hash `password` with `algorithm` and make a new `Password`, which is checked against
+    /// the HIBP range API for that algorithm. Returns `Err` if `password` is empty or longer than
+    /// `MAX_PASSWORD_LEN` bytes.
+    pub fn new_with_algorithm(
+        password: &str,
+        algorithm: HashAlgorithm,
+    ) -> Result<Self, CheckpwnError> {
+        validate_password_len(password.len())?;
+
+        let hash = match algorithm {
+            HashAlgorithm::Sha1 => hash_password(password),
+            HashAlgorithm::Ntlm => api::hash_password_ntlm(password),
+        };
+
+        Ok(Self { hash, algorithm })
+    }
+
+    /// Hash `password` with `hasher` and make a new `Password`, which is checked against the
+    /// HIBP range API for `hasher.algorithm()`. Returns `Err(CheckpwnError::EmptyInput)` if
+    /// `password` is empty, `Err(CheckpwnError::InputTooLarge)` if it is longer than
+    /// `MAX_PASSWORD_LEN` bytes, and `Err(CheckpwnError::InvalidHash)` if `hasher` returns
+    /// anything but a hex digest longer than the 5 character range prefix.
+    pub fn new_with_hasher(
+        password: &str,
+        hasher: &dyn PasswordHasher,
+    ) -> Result<Self, CheckpwnError> {
+        validate_password_len(password.len())?;
+
+        let hash = hasher.hash(password.as_bytes());
+        if hash.len() <= RANGE_PREFIX_LEN || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(CheckpwnError::InvalidHash);
         }
 
         Ok(Self {
-            hash: api::hash_password(password),
+            // HIBP returns uppercase hashes
+            hash: hash.to_uppercase(),
+            algorithm: hasher.algorithm(),
         })
     }
+
+    /// Hash raw bytes and make a new `Password`. Returns `Err` if `bytes` is empty or longer than
+    /// `MAX_PASSWORD_LEN`.
+    ///
+    /// Unlike `Password::new`, `bytes` is hashed exactly as given and need not be UTF-8 text.
+    /// For text passwords, the result is the same as `Password::new` on the UTF-8 string, so
+    /// other encodings (e.g. UTF-16) will not match the passwords known to HIBP.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, CheckpwnError> {
+        validate_password_len(bytes.len())?;
+
+        Ok(Self {
+            hash: api::hash_password_bytes(bytes),
+            algorithm: HashAlgorithm::Sha1,
+        })
+    }
+
+    /// Hash and make a new `Password` for each of `passwords`, in order.
+    /// Each entry is `Err` if the corresponding password is empty or too large.
+    pub fn new_bulk(passwords: &[&str]) -> Vec<Result<Self, CheckpwnError>> {
+        passwords.iter().map(|p| Self::new(p)).collect()
+    }
+
+    /// Hash and make a new `Password` for each of `passwords`, in order.
+    /// Passwords that fail to construct (i.e. empty or too large passwords) are left out.
+    pub fn new_bulk_filter_errors(passwords: &[&str]) -> Vec<Self> {
+        passwords.iter().filter_map(|p| Self::new(p).ok()).collect()
+    }
+
+    /// Make a new `Password` from an already computed SHA1 digest, given as hex.
+    /// Returns `Err` if `hex` is not exactly 40 hexadecimal characters.
+    pub fn from_sha1_hex(hex: &str) -> Result<Self, CheckpwnError> {
+        if !api::validate_sha1_hex(hex) {
+            return Err(CheckpwnError::InvalidHash);
+        }
+
+        Ok(Self {
+            // HIBP returns uppercase hashes
+            hash: hex.to_uppercase(),
+            algorithm: HashAlgorithm::Sha1,
+        })
+    }
+
+    /// Make a new `Password` from a pre-computed SHA1 hash, e.g. one stored by a legacy system.
+    /// The hash is checked as-is, without hashing it again.
+    /// Returns `Err(CheckpwnError::InvalidHash)` if `hex_sha1` is not exactly 40 hexadecimal
+    /// characters.
+    pub fn from_existing_hash(hex_sha1: &str) -> Result<Self, CheckpwnError> {
+        Self::from_sha1_hex(hex_sha1)
+    }
+
+    /// The full uppercase hex hash of the password: SHA1, or NTLM for passwords made with
+    /// `HashAlgorithm::Ntlm`.
+    ///
+    /// This exposes the hash, not the plaintext, but the hash should still be treated as
+    /// sensitive. The same applies to `hash_prefix` and `hash_suffix`.
+    pub fn hash_hex(&self) -> &str {
+        &self.hash
+    }
+
+    /// The first 5 characters of the hash, which is what is sent to the HIBP range API.
+    pub fn hash_prefix(&self) -> &str {
+        &self.hash[..RANGE_PREFIX_LEN]
+    }
+
+    /// The hash without its first 5 characters, which is how HIBP lists it in range responses.
+    pub fn hash_suffix(&self) -> &str {
+        &self.hash[RANGE_PREFIX_LEN..]
+    }
+
+    /// The range API route choice matching the hash algorithm of this password.
+    fn range_choice(&self) -> api::CheckableChoices {
+        match self.algorithm {
+            HashAlgorithm::Sha1 => api::CheckableChoices::Pass,
+            HashAlgorithm::Ntlm => api::CheckableChoices::PassNtlm,
+        }
+    }
 }
 
 impl std::fmt::Debug for Password {
@@ -114,6 +503,63 @@ impl std::fmt::Debug for Password {
     }
 }
 
+impl std::str::FromStr for Password {
+    type Err = CheckpwnError;
+
+    fn from_str(password: &str) -> Result<Self, Self::Err> {
+        Self::new(password)
+    }
+}
+
+impl std::convert::TryFrom<&str> for Password {
+    type Error = CheckpwnError;
+
+    fn try_from(password: &str) -> Result<Self, Self::Error> {
+        Self::new(password)
+    }
+}
+
+impl std::convert::TryFrom<String> for Password {
+    type Error = CheckpwnError;
+
+    /// The plaintext `password` is zeroized after hashing.
+    fn try_from(mut password: String) -> Result<Self, Self::Error> {
+        use zeroize::Zeroize;
+
+        let res = Self::new(&password);
+        password.zeroize();
+        res
+    }
+}
+
+/// Two passwords are equal if and only if their hashes are equal. The hashes are compared in
+/// constant time.
+impl PartialEq for Password {
+    fn eq(&self, other: &Self) -> bool {
+        use subtle::ConstantTimeEq;
+
+        self.hash.as_bytes().ct_eq(other.hash.as_bytes()).into()
+    }
+}
+
+impl Eq for Password {}
+
+impl std::hash::Hash for Password {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.hash.hash(state);
+    }
+}
+
+impl Clone for Password {
+    // The clone gets its own copy of the hash, which is zeroized by its own `Drop`.
+    fn clone(&self) -> Self {
+        Self {
+            hash: self.hash.clone(),
+            algorithm: self.algorithm,
+        }
+    }
+}
+
 impl Drop for Password {
     fn drop(&mut self) {
         use zeroize::Zeroize;
@@ -125,40 +571,586 @@ impl Drop for Password {
 /// Returns Ok(bool), `bool` indicating whether the password is breached or not.
 /// Err() is returned if an error occurred during the check.
 pub fn check_password(password: &Password) -> Result<bool, CheckpwnError> {
-    let pass_db_api_route = api::arg_to_api_route(&api::CheckableChoices::Pass, &password.hash);
+    Ok(check_password_result(password)?.is_breached())
+}
 
-    let agent: ureq::Agent = ureq::AgentBuilder::new()
-        .timeout_connect(time::Duration::from_secs(10))
-        .build();
+/// Check password, returning whether it's breached and how many times it has been seen in breaches.
+/// Err() is returned if an error occurred during the check.
+pub fn check_password_result(password: &Password) -> Result<BreachResult, CheckpwnError> {
+    client::default_client().check_password_result(password)
+}
 
-    let pass_stat = agent
-        .get(&pass_db_api_route)
-        .set("User-Agent", CHECKPWN_USER_AGENT)
-        .set("Add-Padding", "true")
-        .call();
+/// Check password, returning whether it's breached together with the whole range response.
+/// The range response can be reused to check other passwords with the same hash prefix.
+/// Err() is returned if an error occurred during the check.
+pub fn check_password_raw(password: &Password) -> Result<(bool, RangeResponse), CheckpwnError> {
+    client::default_client().check_password_raw(password)
+}
 
-    let request_status = api::response_to_status_codes(&pass_stat)?;
-    // An error here that would abort the check will be returned already from the above
-    // so unwrap() here should be fine
-    let pass_body: String = pass_stat.unwrap().into_string().unwrap();
+/// Check password against an already fetched or locally mirrored `range`, without sending any
+/// request. `range` must be the range of the hash prefix of `password`.
+pub fn check_password_offline(password: &Password, range: &RangeResponse) -> BreachResult {
+    range.count_for_hash(&password.hash).into()
+}
 
-    if api::search_in_range(&pass_body, &password.hash) {
-        if request_status == 200 {
-            Ok(true)
-        } else if request_status == 404 {
-            Ok(false)
-        } else {
-            Err(CheckpwnError::StatusCode)
+/// Check each of `passwords` against `ranges`, keyed by uppercase 5 character hash prefix,
+/// without sending any request. Returns the results in the same order as `passwords`.
+/// A password whose prefix is not in `ranges` is reported as `BreachResult::Clean`.
+pub fn check_password_offline_batch(
+    passwords: &[Password],
+    ranges: &std::collections::HashMap<String, RangeResponse>,
+) -> Vec<BreachResult> {
+    passwords
+        .iter()
+        .map(|password| match ranges.get(password.hash_prefix()) {
+            Some(range) => check_password_offline(password, range),
+            None => BreachResult::Clean,
+        })
+        .collect()
+}
+
+/// Check password, returning how many times it has been seen in breaches.
+/// Returns Ok(u64), `0` if the password is not breached.
+/// Err() is returned if an error occurred during the check.
+pub fn check_password_count(password: &Password) -> Result<u64, CheckpwnError> {
+    Ok(check_password_result(password)?.count())
+}
+
+/// Check password, without asking HIBP to pad the range response.
+/// Padding hides the real size of a response from anyone observing the traffic, so production
+/// code should use `check_password`. This is only meant for testing or controlled environments.
+/// Returns Ok(bool), `bool` indicating whether the password is breached or not.
+/// Err() is returned if an error occurred during the check.
+pub fn check_password_no_padding(password: &Password) -> Result<bool, CheckpwnError> {
+    check_password_padded(password, false)
+}
+
+fn check_password_padded(password: &Password, padding: bool) -> Result<bool, CheckpwnError> {
+    let (request_status, pass_body) =
+        client::default_client().fetch_range(password, padding, None)?;
+
+    evaluate_range(request_status, &pass_body, password)
+}
+
+/// Check password, recording timing and response details of the request in `telemetry`.
+/// Otherwise identical to `check_password`.
+/// The default client has no `RangeCache`, so `cache_hit` is always `false`. Use
+/// `CheckpwnClient::check_password_with_telemetry` with a client built with a `RangeCache` to
+/// record cache hits.
+/// Returns Ok(bool), `bool` indicating whether the password is breached or not.
+/// Err() is returned if an error occurred during the check.
+pub fn check_password_with_telemetry(
+    password: &Password,
+    telemetry: &mut TelemetryCollector,
+) -> Result<bool, CheckpwnError> {
+    client::default_client().check_password_with_telemetry(password, telemetry)
+}
+
+/// Check password, retrying up to `max_retries` times if the request fails with
+/// `CheckpwnError::Network`. The thread sleeps for 500 millis before each retry.
+/// Returns Ok(bool), `bool` indicating whether the password is breached or not.
+/// Err() is returned if the check still fails after all retries, or fails with another error.
+pub fn check_password_resilient(
+    password: &Password,
+    max_retries: u32,
+) -> Result<bool, CheckpwnError> {
+    retry_on_network_error(max_retries, time::Duration::from_millis(500), || {
+        check_password(password)
+    })
+}
+
+fn retry_on_network_error<T, F: FnMut() -> Result<T, CheckpwnError>>(
+    max_retries: u32,
+    delay: time::Duration,
+    mut f: F,
+) -> Result<T, CheckpwnError> {
+    let mut retries = 0;
+    loop {
+        match f() {
+            Err(CheckpwnError::Network) | Err(CheckpwnError::NetworkError(_))
+                if retries < max_retries =>
+            {
+                retries += 1;
+                thread::sleep(delay);
+            }
+            res => return res,
         }
+    }
+}
+
+/// Check password, giving up if the check has not completed by `deadline`.
+/// Returns Ok(bool), `bool` indicating whether the password is breached or not.
+/// `Err(CheckpwnError::Timeout)` is returned if `deadline` has already passed or passes during the
+/// request. Err() is returned if another error occurred during the check.
+pub fn check_password_with_deadline(
+    password: &Password,
+    deadline: time::Instant,
+) -> Result<bool, CheckpwnError> {
+    let remaining = deadline
+        .checked_duration_since(time::Instant::now())
+        .filter(|remaining| !remaining.is_zero())
+        .ok_or(CheckpwnError::Timeout)?;
+
+    let (request_status, pass_body) = client::default_client()
+        .fetch_range(password, true, Some(remaining))
+        .map_err(|err| {
+            if time::Instant::now() >= deadline {
+                CheckpwnError::Timeout
+            } else {
+                err
+            }
+        })?;
+
+    evaluate_range(request_status, &pass_body, password)
+}
+
+/// Decide whether `password` is breached, given the status code and body of its range response.
+fn evaluate_range(
+    request_status: u16,
+    pass_body: &str,
+    password: &Password,
+) -> Result<bool, CheckpwnError> {
+    Ok(evaluate_range_count(request_status, pass_body, password)?.is_some())
+}
+
+/// Check that a password of `len` bytes is neither empty nor larger than `MAX_PASSWORD_LEN`.
+fn validate_password_len(len: usize) -> Result<(), CheckpwnError> {
+    if len == 0 {
+        Err(CheckpwnError::EmptyInput)
+    } else if len > MAX_PASSWORD_LEN {
+        Err(CheckpwnError::InputTooLarge)
     } else {
-        Ok(false)
+        Ok(())
     }
 }
+
+/// Find how many times `password` has been seen in breaches, given the status code and body of
+/// its range response. Returns `None` if the password is not breached.
+fn evaluate_range_count(
+    request_status: u16,
+    pass_body: &str,
+    password: &Password,
+) -> Result<Option<u64>, CheckpwnError> {
+    Ok(RangeResponse::from_response(request_status, pass_body)?.count_for_hash(&password.hash))
+}
+
+#[test]
+fn test_retry_on_network_error() {
+    let delay = time::Duration::from_millis(1);
+
+    // Fails twice, then succeeds
+    let mut calls = 0;
+    let res = retry_on_network_error(3, delay, || {
+        calls += 1;
+        if calls <= 2 {
+            Err(CheckpwnError::Network)
+        } else {
+            Ok(true)
+        }
+    });
+    assert_eq!(res, Ok(true));
+    assert_eq!(calls, 3);
+
+    // Gives up after max_retries
+    let mut calls = 0;
+    let res: Result<bool, CheckpwnError> = retry_on_network_error(2, delay, || {
+        calls += 1;
+        Err(CheckpwnError::Network)
+    });
+    assert_eq!(res, Err(CheckpwnError::Network));
+    assert_eq!(calls, 3);
+
+    // Other errors are not retried
+    let mut calls = 0;
+    let res: Result<bool, CheckpwnError> = retry_on_network_error(3, delay, || {
+        calls += 1;
+        Err(CheckpwnError::InvalidApiKey)
+    });
+    assert_eq!(res, Err(CheckpwnError::InvalidApiKey));
+    assert_eq!(calls, 1);
+}
+
+#[test]
+fn test_check_password_with_deadline_passed() {
+    let password = Password::new("qwerty").unwrap();
+
+    assert_eq!(
+        check_password_with_deadline(&password, time::Instant::now()).unwrap_err(),
+        CheckpwnError::Timeout
+    );
+    let earlier = time::Instant::now() - time::Duration::from_secs(1);
+    assert_eq!(
+        check_password_with_deadline(&password, earlier).unwrap_err(),
+        CheckpwnError::Timeout
+    );
+}
+
 #[test]
 fn test_empty_input_errors() {
     assert!(check_account("", "Test").is_err());
     assert!(check_account("Test", "").is_err());
     assert!(Password::new("").is_err());
+    assert!(check_account_detailed("", "Test").is_err());
+    assert!(check_account_detailed("Test", "").is_err());
+    assert!(check_account_with_sleep("", "Test", |_| {}).is_err());
+    assert!(check_account_with_sleep("Test", "", |_| {}).is_err());
+}
+
+#[test]
+fn test_password_new_with_algorithm() {
+    let sha1 = Password::new_with_algorithm("password", HashAlgorithm::Sha1).unwrap();
+    assert_eq!(sha1.hash, hash_password("password"));
+    assert_eq!(sha1.algorithm, Password::new("password").unwrap().algorithm);
+
+    let ntlm = Password::new_with_algorithm("password", HashAlgorithm::Ntlm).unwrap();
+    assert_eq!(ntlm.hash, "8846F7EAEE8FB117AD06BDD830B7586C");
+    assert_eq!(ntlm.algorithm, HashAlgorithm::Ntlm);
+
+    assert_eq!(
+        Password::new_with_algorithm("", HashAlgorithm::Ntlm).unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+}
+
+#[test]
+fn test_password_input_too_large() {
+    let max = "a".repeat(MAX_PASSWORD_LEN);
+    let too_large = "a".repeat(MAX_PASSWORD_LEN + 1);
+
+    assert!(Password::new(&max).is_ok());
+    assert!(Password::from_bytes(max.as_bytes()).is_ok());
+    assert_eq!(
+        Password::new(&too_large).unwrap_err(),
+        CheckpwnError::InputTooLarge
+    );
+    assert_eq!(
+        Password::new_with_algorithm(&too_large, HashAlgorithm::Ntlm).unwrap_err(),
+        CheckpwnError::InputTooLarge
+    );
+    assert_eq!(
+        Password::new_with_hasher(&too_large, &Sha1Hasher).unwrap_err(),
+        CheckpwnError::InputTooLarge
+    );
+    assert_eq!(
+        Password::from_bytes(too_large.as_bytes()).unwrap_err(),
+        CheckpwnError::InputTooLarge
+    );
+    assert_eq!(
+        Password::new_nfc(&too_large).unwrap_err(),
+        CheckpwnError::InputTooLarge
+    );
+    // The limit is in bytes, not characters
+    assert_eq!(
+        Password::new(&"é".repeat(MAX_PASSWORD_LEN / 2 + 1)).unwrap_err(),
+        CheckpwnError::InputTooLarge
+    );
+}
+
+#[test]
+fn test_password_clone() {
+    let password = Password::new("qwerty").unwrap();
+    let clone = password.clone();
+
+    assert_eq!(clone.hash, password.hash);
+    assert_eq!(clone.algorithm, password.algorithm);
+    assert_eq!(format!("{:?}", clone), format!("{:?}", password));
+
+    // Dropping the clone zeroizes only its own copy
+    drop(clone);
+    assert_eq!(password.hash, hash_password("qwerty"));
+}
+
+#[test]
+fn test_password_from_sha1_hex() {
+    let from_hex = Password::from_sha1_hex("b1b3773a05c0ed0176787a4f1574ff0075f7521e").unwrap();
+    assert_eq!(from_hex.hash, Password::new("qwerty").unwrap().hash);
+
+    assert_eq!(
+        Password::from_sha1_hex("").unwrap_err(),
+        CheckpwnError::InvalidHash
+    );
+    assert_eq!(
+        Password::from_sha1_hex("B1B37").unwrap_err(),
+        CheckpwnError::InvalidHash
+    );
+    assert_eq!(
+        Password::from_sha1_hex("Z1B3773A05C0ED0176787A4F1574FF0075F7521E").unwrap_err(),
+        CheckpwnError::InvalidHash
+    );
+}
+
+#[test]
+fn test_password_hash_accessors() {
+    let password = Password::new("qwerty").unwrap();
+    assert_eq!(
+        password.hash_hex(),
+        "B1B3773A05C0ED0176787A4F1574FF0075F7521E"
+    );
+    assert_eq!(password.hash_prefix(), "B1B37");
+    assert_eq!(
+        password.hash_suffix(),
+        "73A05C0ED0176787A4F1574FF0075F7521E"
+    );
+
+    let ntlm = Password::new_with_algorithm("password", HashAlgorithm::Ntlm).unwrap();
+    assert_eq!(ntlm.hash_prefix(), "8846F");
+    assert_eq!(ntlm.hash_suffix(), "7EAEE8FB117AD06BDD830B7586C");
+}
+
+#[test]
+fn test_password_eq_and_hash() {
+    use std::collections::HashSet;
+
+    assert_eq!(
+        Password::new("qwerty").unwrap(),
+        Password::new("qwerty").unwrap()
+    );
+    assert_eq!(
+        Password::new("qwerty").unwrap(),
+        Password::from_sha1_hex("b1b3773a05c0ed0176787a4f1574ff0075f7521e").unwrap()
+    );
+    assert_ne!(
+        Password::new("qwerty").unwrap(),
+        Password::new("qwertz").unwrap()
+    );
+    assert_ne!(
+        Password::new("qwerty").unwrap(),
+        Password::new_with_algorithm("qwerty", HashAlgorithm::Ntlm).unwrap()
+    );
+
+    let set: HashSet<Password> =
+        Password::new_bulk_filter_errors(&["qwerty", "password", "qwerty"])
+            .into_iter()
+            .collect();
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&Password::new("password").unwrap()));
+}
+
+#[test]
+fn test_password_conversions() {
+    use std::convert::TryFrom;
+
+    let expected = hash_password("qwerty");
+
+    assert_eq!("qwerty".parse::<Password>().unwrap().hash, expected);
+    assert_eq!(Password::try_from("qwerty").unwrap().hash, expected);
+    assert_eq!(
+        Password::try_from(String::from("qwerty")).unwrap().hash,
+        expected
+    );
+
+    assert_eq!(
+        "".parse::<Password>().unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    assert_eq!(
+        Password::try_from("").unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    assert_eq!(
+        Password::try_from(String::new()).unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+}
+
+#[test]
+fn test_password_from_bytes() {
+    assert_eq!(
+        Password::from_bytes(b"qwerty").unwrap().hash,
+        Password::new("qwerty").unwrap().hash
+    );
+    // Not valid UTF-8
+    assert_eq!(
+        Password::from_bytes(&[0xff, 0xfe, 0x00]).unwrap().hash,
+        "B11B4321218546DD157B6DD23F1E783339C4C5EE"
+    );
+    assert_eq!(
+        Password::from_bytes(&[]).unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+}
+
+#[test]
+fn test_password_new_nfc() {
+    let precomposed = "caf\u{e9}";
+    let decomposed = "cafe\u{301}";
+
+    assert_ne!(
+        Password::new(precomposed).unwrap(),
+        Password::new(decomposed).unwrap()
+    );
+    assert_eq!(
+        Password::new_nfc(precomposed).unwrap(),
+        Password::new_nfc(decomposed).unwrap()
+    );
+    assert_eq!(
+        Password::new_nfc(decomposed).unwrap(),
+        Password::new(precomposed).unwrap()
+    );
+    assert_eq!(
+        Password::new_nfc("").unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+}
+
+#[test]
+fn test_password_new_with_hasher() {
+    struct FixedHasher(&'static str);
+
+    impl PasswordHasher for FixedHasher {
+        fn hash(&self, _input: &[u8]) -> String {
+            self.0.to_string()
+        }
+    }
+
+    assert_eq!(
+        Password::new_with_hasher("qwerty", &Sha1Hasher).unwrap(),
+        Password::new("qwerty").unwrap()
+    );
+    assert_eq!(
+        Password::new_with_hasher("password", &NtlmHasher).unwrap(),
+        Password::new_with_algorithm("password", HashAlgorithm::Ntlm).unwrap()
+    );
+
+    let fixed = FixedHasher("b1b3773a05c0ed0176787a4f1574ff0075f7521e");
+    let password = Password::new_with_hasher("anything", &fixed).unwrap();
+    assert_eq!(password, Password::new("qwerty").unwrap());
+    assert_eq!(password.hash_prefix(), "B1B37");
+
+    assert_eq!(
+        Password::new_with_hasher("", &fixed).unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    for invalid in ["", "B1B37", "not a hex digest"].iter() {
+        assert_eq!(
+            Password::new_with_hasher("qwerty", &FixedHasher(invalid)).unwrap_err(),
+            CheckpwnError::InvalidHash
+        );
+    }
+}
+
+#[test]
+fn test_check_password_offline() {
+    let range = RangeResponse::new(
+        "73678F196DE938F721CD408ED190330F5DB:3\r\n73A05C0ED0176787A4F1574FF0075F7521E:3752262"
+            .into(),
+    )
+    .unwrap();
+    let qwerty = Password::new("qwerty").unwrap();
+    let password = Password::new("password").unwrap();
+
+    assert_eq!(
+        check_password_offline(&qwerty, &range),
+        BreachResult::Breached { count: 3752262 }
+    );
+    assert_eq!(
+        check_password_offline(&password, &range),
+        BreachResult::Clean
+    );
+
+    let mut ranges = std::collections::HashMap::new();
+    ranges.insert("B1B37".to_string(), range);
+    ranges.insert(
+        "5BAA6".to_string(),
+        RangeResponse::new("1E4C9B93F3F0682250B6CF8331B7EE68FD8:10434004".into()).unwrap(),
+    );
+    let not_mirrored = Password::new("dHRUKbDaKgIobOtX").unwrap();
+
+    assert_eq!(
+        check_password_offline_batch(&[qwerty, password, not_mirrored], &ranges),
+        vec![
+            BreachResult::Breached { count: 3752262 },
+            BreachResult::Breached { count: 10434004 },
+            BreachResult::Clean,
+        ]
+    );
+}
+
+#[test]
+fn test_password_from_existing_hash() {
+    let existing =
+        Password::from_existing_hash("B1B3773A05C0ED0176787A4F1574FF0075F7521E").unwrap();
+    assert_eq!(existing.hash, Password::new("qwerty").unwrap().hash);
+    let lowercase =
+        Password::from_existing_hash("b1b3773a05c0ed0176787a4f1574ff0075f7521e").unwrap();
+    assert_eq!(lowercase.hash, existing.hash);
+
+    for invalid in [
+        "",
+        // Too short
+        "B1B3773A05C0ED0176787A4F1574FF0075F7521",
+        // Too long
+        "B1B3773A05C0ED0176787A4F1574FF0075F7521E0",
+        // Not hex
+        "G1B3773A05C0ED0176787A4F1574FF0075F7521E",
+        // Not ASCII
+        "é1B3773A05C0ED0176787A4F1574FF0075F7521",
+    ]
+    .iter()
+    {
+        assert_eq!(
+            Password::from_existing_hash(invalid).unwrap_err(),
+            CheckpwnError::InvalidHash
+        );
+    }
+}
+
+#[test]
+fn test_password_new_bulk() {
+    let bulk = Password::new_bulk(&["qwerty", "", "password"]);
+    assert_eq!(bulk.len(), 3);
+    assert_eq!(bulk[0].as_ref().unwrap().hash, hash_password("qwerty"));
+    assert_eq!(bulk[1].as_ref().unwrap_err(), &CheckpwnError::EmptyInput);
+    assert_eq!(bulk[2].as_ref().unwrap().hash, hash_password("password"));
+
+    let filtered = Password::new_bulk_filter_errors(&["", "qwerty", "", "password"]);
+    assert_eq!(filtered.len(), 2);
+    assert_eq!(filtered[0].hash, hash_password("qwerty"));
+    assert_eq!(filtered[1].hash, hash_password("password"));
+
+    assert!(Password::new_bulk(&[]).is_empty());
+    assert!(Password::new_bulk_filter_errors(&["", ""]).is_empty());
+}
+
+#[test]
+fn test_check_account_email_only_invalid() {
+    assert_eq!(
+        check_account_email_only("", "Test").unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    assert_eq!(
+        check_account_email_only("test@example.com", "").unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+
+    for invalid in ["username", "test@", "@example.com", "test@example"].iter() {
+        assert_eq!(
+            check_account_email_only(invalid, "Test").unwrap_err(),
+            CheckpwnError::BadResponse
+        );
+    }
+}
+
+#[test]
+fn test_check_accounts_sequential_progress() {
+    let calls = std::cell::RefCell::new(Vec::new());
+    // Empty API key fails before any request is sent or the thread sleeps.
+    let results = check_accounts_sequential(
+        &["first", "second"],
+        "",
+        Some(|current: usize, total: usize| calls.borrow_mut().push((current, total))),
+    );
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0, "first");
+    assert_eq!(results[1].0, "second");
+    assert!(results
+        .iter()
+        .all(|(_, res)| *res == Err(CheckpwnError::EmptyInput)));
+    assert_eq!(*calls.borrow(), vec![(0, 2), (1, 2)]);
+
+    let no_progress: Option<fn(usize, usize)> = None;
+    assert!(check_accounts_sequential(&[], "", no_progress).is_empty());
 }
 
 #[cfg(test)]
@@ -192,6 +1184,145 @@ fn test_check_account() {
     assert!(!check_account(&rnd_email, &api_key).unwrap());
 }
 
+#[cfg(feature = "ci_test")]
+#[test]
+fn test_check_account_typed() {
+    let api_key = ApiKey::from_env("API_KEY").unwrap();
+
+    assert!(check_account_typed(&Account::new("test@example.com").unwrap(), &api_key).unwrap());
+}
+
+#[cfg(feature = "ci_test")]
+#[test]
+fn test_check_account_email() {
+    let api_key = ApiKey::from_env("API_KEY").unwrap();
+
+    assert!(check_account_email(&Email::new("test@example.com").unwrap(), &api_key).unwrap());
+}
+
+#[cfg(feature = "ci_test")]
+#[test]
+fn test_check_password_count() {
+    assert!(check_password_count(&Password::new("qwerty").unwrap()).unwrap() > 0);
+    assert_eq!(
+        check_password_count(&Password::new("dHRUKbDaKgIobOtX").unwrap()).unwrap(),
+        0
+    );
+}
+
+#[cfg(feature = "ci_test")]
+#[test]
+fn test_check_password_with_deadline() {
+    let deadline = time::Instant::now() + time::Duration::from_secs(60);
+
+    assert!(check_password_with_deadline(&Password::new("qwerty").unwrap(), deadline).unwrap());
+}
+
+#[cfg(feature = "ci_test")]
+#[test]
+fn test_check_account_detailed() {
+    let api_key = get_env_api_key_from_ci();
+
+    assert!(
+        check_account_detailed("test@example.com", &api_key)
+            .unwrap()
+            .account_breached
+    );
+}
+
+#[cfg(all(feature = "ci_test", feature = "serde"))]
+#[test]
+fn test_get_breach_details() {
+    let api_key = get_env_api_key_from_ci();
+    let breaches = get_breach_details("test@example.com", &api_key).unwrap();
+
+    assert!(!breaches.is_empty());
+    assert!(breaches.iter().all(|breach| !breach.name.is_empty()));
+}
+
+#[cfg(all(feature = "ci_test", feature = "serde"))]
+#[test]
+fn test_breach_info_endpoints() {
+    let api_key = ApiKey::from_env("API_KEY").unwrap();
+
+    assert!(!get_all_breaches(&api_key).unwrap().is_empty());
+    assert_eq!(get_breach_by_name("Adobe", &api_key).unwrap().name, "Adobe");
+    assert!(get_data_classes(&api_key)
+        .unwrap()
+        .iter()
+        .any(|class| class == "Email addresses"));
+    assert!(!get_latest_breach(&api_key).unwrap().name.is_empty());
+    assert!(
+        get_subscription_status(&api_key)
+            .unwrap()
+            .rate_limit_request_per_minute
+            > 0
+    );
+}
+
+#[cfg(feature = "ci_test")]
+#[test]
+fn test_check_account_email_only() {
+    let api_key = get_env_api_key_from_ci();
+
+    assert!(check_account_email_only("test@example.com", &api_key).unwrap());
+}
+
+#[test]
+fn test_evaluate_range_count() {
+    let password = Password::new("qwerty").unwrap();
+    let body = "73678F196DE938F721CD408ED190330F5DB:3
+73A05C0ED0176787A4F1574FF0075F7521E:3752262
+748186F058DA83745B80E70B66D36B216A4:4";
+
+    assert_eq!(
+        evaluate_range_count(200, body, &password).unwrap(),
+        Some(3752262)
+    );
+    assert_eq!(evaluate_range_count(404, body, &password).unwrap(), None);
+    assert_eq!(
+        evaluate_range_count(200, "748186F058DA83745B80E70B66D36B216A4:4", &password).unwrap(),
+        None
+    );
+    assert!(evaluate_range(200, body, &password).unwrap());
+    assert_eq!(
+        evaluate_range_count(503, "", &password),
+        Err(CheckpwnError::ServiceUnavailable)
+    );
+    assert_eq!(
+        evaluate_range_count(500, body, &password),
+        Err(CheckpwnError::UnexpectedStatus(500))
+    );
+    // Statuses other than 200 are never decided by the body
+    assert_eq!(evaluate_range_count(404, "<html>", &password), Ok(None));
+    assert_eq!(
+        evaluate_range_count(500, "748186F058DA83745B80E70B66D36B216A4:4", &password),
+        Err(CheckpwnError::UnexpectedStatus(500))
+    );
+    assert_eq!(
+        evaluate_range_count(200, "<html>", &password),
+        Err(CheckpwnError::MalformedResponse)
+    );
+    assert_eq!(
+        evaluate_range_count(200, "\r\n", &password),
+        Err(CheckpwnError::MalformedResponse)
+    );
+}
+
+#[cfg(feature = "ci_test")]
+#[test]
+fn test_check_password_result() {
+    let breached = check_password_result(&Password::new("qwerty").unwrap()).unwrap();
+    assert!(breached == true);
+    assert!(breached.count() > 0);
+
+    assert_eq!(
+        check_password_result(&Password::new("dHRUKbDaKgIobOtX").unwrap()).unwrap(),
+        BreachResult::Clean
+    );
+}
+
+#[cfg(feature = "ci_test")]
 #[test]
 fn test_check_password() {
     let breached_password = Password::new("qwerty").unwrap();
@@ -0,0 +1,82 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::CheckpwnError;
+use std::time::Duration;
+
+/// Callbacks for the requests a `CheckpwnClient` sends, set with `CheckpwnClientBuilder::metrics`.
+/// Implement this to export metrics to e.g. Prometheus or OpenTelemetry.
+///
+/// `endpoint` names the HIBP API requested, e.g. "range" or "breachedaccount". It never contains
+/// the account, password hash or API key of the request.
+pub trait Metrics: Send + Sync {
+    /// Called before each request is sent, including retries.
+    fn on_request_sent(&self, endpoint: &str);
+
+    /// Called when a response is received, with its status code and the time since the request
+    /// was sent.
+    fn on_response_received(&self, endpoint: &str, status: u16, elapsed: Duration);
+
+    /// Called when a request fails without a response, e.g. with `CheckpwnError::Network`.
+    fn on_error(&self, endpoint: &str, error: &CheckpwnError);
+}
+
+/// `Metrics` that ignore all callbacks. This is the default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct NoopMetrics;
+
+impl Metrics for NoopMetrics {
+    fn on_request_sent(&self, _endpoint: &str) {}
+
+    fn on_response_received(&self, _endpoint: &str, _status: u16, _elapsed: Duration) {}
+
+    fn on_error(&self, _endpoint: &str, _error: &CheckpwnError) {}
+}
+
+/// Name of the HIBP API requested by `url`, for use as the `endpoint` of `Metrics`.
+pub(crate) fn endpoint_name(url: &str) -> &str {
+    let path = url.splitn(4, '/').nth(3).unwrap_or("");
+    let path = path.strip_prefix("api/v3/").unwrap_or(path);
+
+    path.split(['/', '?']).next().unwrap_or(path)
+}
+
+#[test]
+fn test_endpoint_name() {
+    assert_eq!(
+        endpoint_name("https://api.pwnedpasswords.com/range/B1B37"),
+        "range"
+    );
+    assert_eq!(
+        endpoint_name("https://api.pwnedpasswords.com/range/8846F?mode=ntlm"),
+        "range"
+    );
+    assert_eq!(
+        endpoint_name("https://haveibeenpwned.com/api/v3/breachedaccount/test%40example.com"),
+        "breachedaccount"
+    );
+    assert_eq!(
+        endpoint_name("https://haveibeenpwned.com/api/v3/breaches"),
+        "breaches"
+    );
+    assert_eq!(endpoint_name("not a url"), "");
+}
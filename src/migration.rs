@@ -0,0 +1,165 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::client::{default_client, CheckpwnClient};
+use crate::errors::CheckpwnError;
+
+/// Result of checking one account with two different API keys, from `diff_accounts`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BreachDiff {
+    /// The account that was checked.
+    pub account: String,
+    /// Whether the account is breached, according to the old API key.
+    pub old_key_breached: bool,
+    /// Whether the account is breached, according to the new API key.
+    pub new_key_breached: bool,
+}
+
+impl BreachDiff {
+    /// Returns `true` if both API keys gave the same result for the account.
+    pub fn is_empty(&self) -> bool {
+        self.old_key_breached == self.new_key_breached
+    }
+}
+
+/// Check each account with both `old_api_key` and `new_api_key`, to verify that both keys give
+/// the same results, e.g. when rotating keys.
+/// A `BreachDiff` is returned for every account, in order. If all of them are empty, the keys are
+/// functionally equivalent for the accounts given.
+/// Like `check_account`, the thread sleeps for `DEFAULT_RATE_LIMIT_DELAY` before checking each
+/// account, once for both keys.
+/// Err() is returned on the first error that occurs with either key.
+pub fn diff_accounts(
+    old_api_key: &str,
+    new_api_key: &str,
+    accounts: &[&str],
+) -> Result<Vec<BreachDiff>, CheckpwnError> {
+    diff_accounts_with(default_client(), old_api_key, new_api_key, accounts)
+}
+
+fn diff_accounts_with(
+    client: &CheckpwnClient,
+    old_api_key: &str,
+    new_api_key: &str,
+    accounts: &[&str],
+) -> Result<Vec<BreachDiff>, CheckpwnError> {
+    if old_api_key.is_empty() || new_api_key.is_empty() {
+        return Err(CheckpwnError::EmptyInput);
+    }
+
+    let mut diffs = Vec::with_capacity(accounts.len());
+    for account in accounts {
+        diffs.push(BreachDiff {
+            account: account.to_string(),
+            old_key_breached: client.check_account(account, old_api_key)?,
+            // The rate limit was already waited for, before checking with the old key
+            new_key_breached: client.check_account_with_sleep(account, new_api_key, |_| {})?,
+        });
+    }
+
+    Ok(diffs)
+}
+
+#[test]
+fn test_breach_diff_is_empty() {
+    let same = BreachDiff {
+        account: "test@example.com".to_string(),
+        old_key_breached: true,
+        new_key_breached: true,
+    };
+    let different = BreachDiff {
+        new_key_breached: false,
+        ..same.clone()
+    };
+
+    assert!(same.is_empty());
+    assert!(!different.is_empty());
+}
+
+#[test]
+fn test_diff_accounts_empty_input() {
+    assert_eq!(
+        diff_accounts("", "Test", &["test@example.com"]).unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    assert_eq!(
+        diff_accounts("Test", "", &["test@example.com"]).unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    assert_eq!(
+        diff_accounts("Test", "Test", &[""]).unwrap_err(),
+        CheckpwnError::EmptyInput
+    );
+    assert!(diff_accounts("Test", "Test", &[]).unwrap().is_empty());
+}
+
+#[test]
+fn test_diff_accounts_sleeps_once_per_account() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let mock = Arc::new(
+        crate::testing::MockHttpClient::new()
+            .with_response(
+                "https://haveibeenpwned.com/api/v3/breachedaccount/first%40example.com",
+                200,
+                "[]",
+            )
+            .with_response(
+                "https://haveibeenpwned.com/api/v3/pasteaccount/first%40example.com",
+                404,
+                "",
+            )
+            .with_response(
+                "https://haveibeenpwned.com/api/v3/breachedaccount/second%40example.com",
+                404,
+                "",
+            )
+            .with_response(
+                "https://haveibeenpwned.com/api/v3/pasteaccount/second%40example.com",
+                404,
+                "",
+            ),
+    );
+    let sleeps = Arc::new(AtomicUsize::new(0));
+    let counter = sleeps.clone();
+    let client = CheckpwnClient::builder()
+        .http_client(mock.clone())
+        .sleep_fn(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+        })
+        .build();
+
+    let diffs = diff_accounts_with(
+        &client,
+        "old",
+        "new",
+        &["first@example.com", "second@example.com"],
+    )
+    .unwrap();
+
+    assert_eq!(diffs.len(), 2);
+    assert!(diffs[0].old_key_breached && diffs[0].new_key_breached);
+    assert!(!diffs[1].old_key_breached && !diffs[1].new_key_breached);
+    assert_eq!(sleeps.load(Ordering::SeqCst), 2);
+    assert_eq!(mock.calls().len(), 8);
+}
@@ -0,0 +1,148 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::api;
+use crate::errors::CheckpwnError;
+
+/// The body of a response from the HIBP range API: one `SUFFIX:COUNT` entry per line, for
+/// every hash sharing the same 5 character prefix.
+///
+/// A `RangeResponse` can be kept around to check more passwords sharing its prefix without
+/// sending another request.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RangeResponse(String);
+
+impl RangeResponse {
+    /// Make a new `RangeResponse` from the body of a range response.
+    /// Returns `Err(CheckpwnError::MalformedResponse)` if a non-empty line is not a
+    /// `SUFFIX:COUNT` pair.
+    pub fn new(body: String) -> Result<Self, CheckpwnError> {
+        for line in body.lines().filter(|line| !line.trim().is_empty()) {
+            parse_entry(line)?;
+        }
+
+        Ok(Self(body))
+    }
+
+    /// Make a `RangeResponse` from the status code and body of a range response. A range that
+    /// HIBP doesn't have (status code 404) is empty.
+    pub(crate) fn from_response(status: u16, body: &str) -> Result<Self, CheckpwnError> {
+        match status {
+            // A range always has entries, even more so with padding.
+            200 if body.trim().is_empty() => Err(CheckpwnError::MalformedResponse),
+            200 => Self::new(body.to_string()),
+            // A range that isn't found can't contain the hash, whatever the body is.
+            404 => Ok(Self::default()),
+            503 => Err(CheckpwnError::ServiceUnavailable),
+            code => Err(CheckpwnError::UnexpectedStatus(code)),
+        }
+    }
+
+    /// Returns `true` if `full_hash`, the full hex hash including its prefix, is in the range.
+    pub fn contains_hash(&self, full_hash: &str) -> bool {
+        self.count_for_hash(full_hash).is_some()
+    }
+
+    /// Number of times `full_hash`, the full hex hash including its prefix, has been seen in
+    /// breaches, or `None` if it's not in the range.
+    pub fn count_for_hash(&self, full_hash: &str) -> Option<u64> {
+        // Every line has been validated in `new`, so this can only fail if `full_hash` is too
+        // short to have a suffix, in which case it is not in the range either.
+        api::search_in_range(&self.0, &full_hash.to_uppercase())
+            .ok()
+            .flatten()
+    }
+
+    /// The `(suffix, count)` pairs of the range, in the order HIBP returned them.
+    /// Padding entries, which always have a count of 0, are left out.
+    pub fn entries(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.0
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| parse_entry(line).ok())
+            .filter(|(_, count)| *count != 0)
+    }
+
+    /// The body of the range response.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+fn parse_entry(line: &str) -> Result<(&str, u64), CheckpwnError> {
+    let (suffix, count) = line
+        .split_once(':')
+        .ok_or(CheckpwnError::MalformedResponse)?;
+
+    Ok((suffix, count.trim().parse()?))
+}
+
+#[test]
+fn test_range_response() {
+    let range = RangeResponse::new(
+        "73678F196DE938F721CD408ED190330F5DB:3\r\n73A05C0ED0176787A4F1574FF0075F7521E:3752262\r\n7FF32ECF384A7DBD7F1325F2AA9421747D8:0\r\n".into(),
+    )
+    .unwrap();
+
+    // "qwerty"
+    assert!(range.contains_hash("B1B3773A05C0ED0176787A4F1574FF0075F7521E"));
+    assert_eq!(
+        range.count_for_hash("b1b3773a05c0ed0176787a4f1574ff0075f7521e"),
+        Some(3752262)
+    );
+    assert!(!range.contains_hash("B1B377FF32ECF384A7DBD7F1325F2AA9421747D8"));
+    assert_eq!(range.count_for_hash("B1B37"), None);
+
+    assert_eq!(
+        range.entries().collect::<Vec<_>>(),
+        vec![
+            ("73678F196DE938F721CD408ED190330F5DB", 3),
+            ("73A05C0ED0176787A4F1574FF0075F7521E", 3752262),
+        ]
+    );
+
+    assert_eq!(
+        RangeResponse::new("<html>".into()).unwrap_err(),
+        CheckpwnError::MalformedResponse
+    );
+    assert_eq!(RangeResponse::default().entries().count(), 0);
+}
+
+#[test]
+fn test_range_response_from_response() {
+    assert_eq!(
+        RangeResponse::from_response(404, "<html>").unwrap(),
+        RangeResponse::default()
+    );
+    assert_eq!(
+        RangeResponse::from_response(200, "\r\n"),
+        Err(CheckpwnError::MalformedResponse)
+    );
+    assert_eq!(
+        RangeResponse::from_response(503, ""),
+        Err(CheckpwnError::ServiceUnavailable)
+    );
+    assert_eq!(
+        RangeResponse::from_response(500, "73A05C0ED0176787A4F1574FF0075F7521E:4"),
+        Err(CheckpwnError::UnexpectedStatus(500))
+    );
+}
@@ -0,0 +1,192 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::{thread, time};
+
+/// Strategy for waiting before each request to the HIBP account APIs, set with
+/// `CheckpwnClientBuilder::rate_limit_strategy`.
+pub trait RateLimitStrategy: Send + Sync {
+    /// Block until the next request may be sent.
+    fn wait(&self);
+}
+
+/// Sleep for a fixed duration before each request. This is the default, with
+/// `DEFAULT_RATE_LIMIT_DELAY`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedDelay(pub time::Duration);
+
+impl RateLimitStrategy for FixedDelay {
+    fn wait(&self) {
+        debug_event!("rate limit sleep = {} ms", self.0.as_millis());
+        thread::sleep(self.0);
+    }
+}
+
+/// Call a custom sleep function with a fixed duration before each request, set with
+/// `CheckpwnClientBuilder::sleep_fn`.
+pub(crate) struct SleepFn {
+    pub(crate) delay: time::Duration,
+    pub(crate) sleep: Arc<dyn Fn(time::Duration) + Send + Sync>,
+}
+
+impl RateLimitStrategy for SleepFn {
+    fn wait(&self) {
+        debug_event!("rate limit sleep = {} ms", self.delay.as_millis());
+        (self.sleep)(self.delay);
+    }
+}
+
+/// Never wait. For callers managing the HIBP rate limit themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NoDelay;
+
+impl RateLimitStrategy for NoDelay {
+    fn wait(&self) {}
+}
+
+/// A token bucket shared by all callers: up to `capacity` requests may be sent at once, after
+/// which one request is allowed per `refill_rate`.
+#[derive(Debug)]
+pub struct TokenBucket {
+    capacity: u32,
+    refill_rate: time::Duration,
+    tokens: AtomicU64,
+    last_refill: Mutex<time::Instant>,
+}
+
+impl TokenBucket {
+    /// Make a new, full `TokenBucket`. A `capacity` of 0 is treated as 1.
+    pub fn new(capacity: u32, refill_rate: time::Duration) -> Self {
+        let capacity = capacity.max(1);
+        Self {
+            capacity,
+            refill_rate,
+            tokens: AtomicU64::new(u64::from(capacity)),
+            last_refill: Mutex::new(time::Instant::now()),
+        }
+    }
+
+    /// Take a token if one is available, or return how long to wait until the next one is.
+    fn try_take(&self) -> Result<(), time::Duration> {
+        let mut last_refill = self.last_refill.lock().unwrap_or_else(|e| e.into_inner());
+        let now = time::Instant::now();
+        let capacity = u64::from(self.capacity);
+
+        if self.refill_rate.is_zero() {
+            self.tokens.store(capacity, Ordering::SeqCst);
+        } else {
+            let elapsed = now.duration_since(*last_refill);
+            let refilled = (elapsed.as_nanos() / self.refill_rate.as_nanos()) as u64;
+            if refilled > 0 {
+                let tokens = self.tokens.load(Ordering::SeqCst).saturating_add(refilled);
+                if tokens >= capacity {
+                    self.tokens.store(capacity, Ordering::SeqCst);
+                    *last_refill = now;
+                } else {
+                    self.tokens.store(tokens, Ordering::SeqCst);
+                    // Keep the remainder, so that partial refills aren't lost
+                    *last_refill += self.refill_rate * refilled as u32;
+                }
+            }
+        }
+
+        if self.tokens.load(Ordering::SeqCst) > 0 {
+            self.tokens.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        } else {
+            Err(self
+                .refill_rate
+                .saturating_sub(now.duration_since(*last_refill)))
+        }
+    }
+}
+
+impl RateLimitStrategy for TokenBucket {
+    fn wait(&self) {
+        while let Err(remaining) = self.try_take() {
+            debug_event!("rate limit sleep = {} ms", remaining.as_millis());
+            thread::sleep(remaining);
+        }
+    }
+}
+
+#[test]
+fn test_fixed_and_no_delay() {
+    let start = time::Instant::now();
+    NoDelay.wait();
+    FixedDelay(time::Duration::from_millis(20)).wait();
+    assert!(start.elapsed() >= time::Duration::from_millis(20));
+}
+
+#[test]
+fn test_token_bucket() {
+    let bucket = TokenBucket::new(2, time::Duration::from_millis(50));
+
+    // The first `capacity` requests are allowed immediately
+    assert_eq!(bucket.try_take(), Ok(()));
+    assert_eq!(bucket.try_take(), Ok(()));
+    let remaining = bucket.try_take().unwrap_err();
+    assert!(remaining <= time::Duration::from_millis(50));
+
+    let start = time::Instant::now();
+    bucket.wait();
+    assert!(start.elapsed() >= remaining);
+    assert!(bucket.try_take().is_err());
+}
+
+#[test]
+fn test_token_bucket_zero_capacity() {
+    let bucket = TokenBucket::new(0, time::Duration::from_millis(20));
+    assert_eq!(bucket.try_take(), Ok(()));
+    assert!(bucket.try_take().is_err());
+
+    let start = time::Instant::now();
+    bucket.wait();
+    assert!(start.elapsed() < time::Duration::from_secs(1));
+
+    // Without a refill rate, every request is allowed
+    let bucket = TokenBucket::new(0, time::Duration::ZERO);
+    for _ in 0..3 {
+        bucket.wait();
+    }
+}
+
+#[test]
+fn test_token_bucket_concurrent() {
+    let bucket = Arc::new(TokenBucket::new(1, time::Duration::from_millis(20)));
+    let start = time::Instant::now();
+
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let bucket = bucket.clone();
+            thread::spawn(move || bucket.wait())
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+
+    // One request goes through immediately, the other three wait for a refill each
+    assert!(start.elapsed() >= time::Duration::from_millis(60));
+}
@@ -0,0 +1,168 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+/// Result of checking a password at HIBP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum BreachResult {
+    /// The password has been seen `count` times in breaches.
+    Breached {
+        /// Number of times the password has been seen in breaches.
+        count: u64,
+    },
+    /// The password has not been seen in any breaches.
+    Clean,
+}
+
+impl BreachResult {
+    /// Returns `true` if the password has been seen in breaches.
+    pub fn is_breached(&self) -> bool {
+        matches!(self, BreachResult::Breached { .. })
+    }
+
+    /// Number of times the password has been seen in breaches. `0` if `Clean`.
+    pub fn count(&self) -> u64 {
+        match self {
+            BreachResult::Breached { count } => *count,
+            BreachResult::Clean => 0,
+        }
+    }
+}
+
+impl From<Option<u64>> for BreachResult {
+    fn from(count: Option<u64>) -> Self {
+        match count {
+            Some(count) => BreachResult::Breached { count },
+            None => BreachResult::Clean,
+        }
+    }
+}
+
+/// Compares `is_breached()` with the `bool`, to ease migrating from `check_password`.
+impl PartialEq<bool> for BreachResult {
+    fn eq(&self, other: &bool) -> bool {
+        self.is_breached() == *other
+    }
+}
+
+/// Result of checking an account at HIBP, on both account and paste databases.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct AccountBreachResult {
+    /// Whether the account was found in the breached account database.
+    pub account_breached: bool,
+    /// Whether the account was found in the paste database.
+    pub paste_breached: bool,
+}
+
+impl AccountBreachResult {
+    /// Returns `true` if the account was found in either database.
+    pub fn any_breached(&self) -> bool {
+        self.account_breached || self.paste_breached
+    }
+
+    /// Returns `true` if the account was found in both databases.
+    pub fn both_breached(&self) -> bool {
+        self.account_breached && self.paste_breached
+    }
+}
+
+#[test]
+fn test_account_breach_result() {
+    let none = AccountBreachResult {
+        account_breached: false,
+        paste_breached: false,
+    };
+    let acc = AccountBreachResult {
+        account_breached: true,
+        paste_breached: false,
+    };
+    let paste = AccountBreachResult {
+        account_breached: false,
+        paste_breached: true,
+    };
+    let both = AccountBreachResult {
+        account_breached: true,
+        paste_breached: true,
+    };
+
+    assert!(!none.any_breached());
+    assert!(!none.both_breached());
+    assert!(acc.any_breached());
+    assert!(!acc.both_breached());
+    assert!(paste.any_breached());
+    assert!(!paste.both_breached());
+    assert!(both.any_breached());
+    assert!(both.both_breached());
+}
+
+#[test]
+fn test_breach_result() {
+    let breached = BreachResult::Breached { count: 3752262 };
+    let clean = BreachResult::Clean;
+
+    assert!(breached.is_breached());
+    assert_eq!(breached.count(), 3752262);
+    assert!(breached == true);
+    assert!(breached != false);
+
+    assert!(!clean.is_breached());
+    assert_eq!(clean.count(), 0);
+    assert!(clean == false);
+    assert!(clean != true);
+
+    assert_eq!(
+        BreachResult::from(Some(3)),
+        BreachResult::Breached { count: 3 }
+    );
+    assert_eq!(BreachResult::from(None), BreachResult::Clean);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_results_round_trip() {
+    let breached = BreachResult::Breached { count: 3752262 };
+    let json = serde_json::to_string(&breached).unwrap();
+    assert_eq!(json, r#"{"Breached":{"count":3752262}}"#);
+    assert_eq!(
+        serde_json::from_str::<BreachResult>(&json).unwrap(),
+        breached
+    );
+
+    let json = serde_json::to_string(&BreachResult::Clean).unwrap();
+    assert_eq!(json, r#""Clean""#);
+    assert_eq!(
+        serde_json::from_str::<BreachResult>(&json).unwrap(),
+        BreachResult::Clean
+    );
+
+    let account = AccountBreachResult {
+        account_breached: true,
+        paste_breached: false,
+    };
+    let json = serde_json::to_string(&account).unwrap();
+    assert_eq!(json, r#"{"account_breached":true,"paste_breached":false}"#);
+    assert_eq!(
+        serde_json::from_str::<AccountBreachResult>(&json).unwrap(),
+        account
+    );
+}
@@ -0,0 +1,262 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use crate::errors::CheckpwnError;
+use std::{thread, time};
+
+/// Configuration of how `CheckpwnClient` retries failed requests, set with
+/// `CheckpwnClientBuilder::retry_config`.
+///
+/// Requests failing with `CheckpwnError::Network`, `CheckpwnError::NetworkError` or
+/// `CheckpwnError::Timeout` are retried with an
+/// exponential backoff: the n-th retry sleeps for `base_delay * 2^(n - 1)`, capped at `max_delay`.
+/// Requests that HIBP rate limits are retried after sleeping for the duration given in its
+/// `Retry-After` header. Other errors, such as `CheckpwnError::InvalidApiKey` or
+/// `CheckpwnError::EmptyInput`, are never retried.
+///
+/// Without a `RetryConfig`, a `CheckpwnClient` returns all errors to the caller immediately.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Maximum number of times a request is sent, including the first attempt.
+    pub max_attempts: u8,
+    /// Delay before the first retry.
+    pub base_delay: time::Duration,
+    /// Upper bound for the delay before any retry.
+    pub max_delay: time::Duration,
+    /// Whether to randomize each delay to between half and all of its value,
+    /// so that concurrent clients don't retry in lockstep.
+    pub jitter: bool,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: time::Duration::from_secs(1),
+            max_delay: time::Duration::from_secs(30),
+            jitter: true,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// Call `request` until it succeeds, fails with an error that is not retried, or
+    /// `max_attempts` is reached.
+    pub(crate) fn run<T, F: FnMut() -> Result<T, CheckpwnError>>(
+        &self,
+        request: F,
+    ) -> Result<T, CheckpwnError> {
+        self.run_with_sleep(request, thread::sleep)
+    }
+
+    fn run_with_sleep<T, F, S>(&self, mut request: F, sleep: S) -> Result<T, CheckpwnError>
+    where
+        F: FnMut() -> Result<T, CheckpwnError>,
+        S: Fn(time::Duration),
+    {
+        let mut attempt = 1;
+        loop {
+            match request() {
+                Err(CheckpwnError::RateLimited(retry_after)) if attempt < self.max_attempts => {
+                    sleep(retry_after);
+                }
+                Err(CheckpwnError::Network)
+                | Err(CheckpwnError::NetworkError(_))
+                | Err(CheckpwnError::Timeout)
+                    if attempt < self.max_attempts =>
+                {
+                    sleep(self.backoff(attempt));
+                }
+                res => return res,
+            }
+            attempt += 1;
+        }
+    }
+
+    /// Delay before the retry following the `attempt`-th attempt.
+    fn backoff(&self, attempt: u8) -> time::Duration {
+        let factor = 1u32.checked_shl(u32::from(attempt) - 1).unwrap_or(u32::MAX);
+        let delay = self
+            .base_delay
+            .checked_mul(factor)
+            .unwrap_or(self.max_delay)
+            .min(self.max_delay);
+
+        if self.jitter {
+            let half = delay / 2;
+            half + half.mul_f64(random_fraction())
+        } else {
+            delay
+        }
+    }
+}
+
+/// A random number in `[0, 1]`. Only meant for jitter, so it doesn't need to be a good RNG.
+fn random_fraction() -> f64 {
+    use std::collections::hash_map::RandomState;
+    use std::hash::{BuildHasher, Hasher};
+
+    let random = RandomState::new().build_hasher().finish();
+    random as f64 / u64::MAX as f64
+}
+
+#[test]
+fn test_retry_rate_limited() {
+    let config = RetryConfig {
+        jitter: false,
+        ..RetryConfig::default()
+    };
+    let slept = std::cell::RefCell::new(Vec::new());
+    let sleep = |d: time::Duration| slept.borrow_mut().push(d);
+
+    let mut calls = 0;
+    let res = config.run_with_sleep(
+        || {
+            calls += 1;
+            if calls == 1 {
+                Err(CheckpwnError::RateLimited(time::Duration::from_secs(2)))
+            } else {
+                Ok(calls)
+            }
+        },
+        sleep,
+    );
+    assert_eq!(res, Ok(2));
+    assert_eq!(*slept.borrow(), vec![time::Duration::from_secs(2)]);
+
+    // Gives up after max_attempts
+    let mut calls = 0;
+    let res: Result<(), CheckpwnError> = config.run_with_sleep(
+        || {
+            calls += 1;
+            Err(CheckpwnError::RateLimited(time::Duration::from_secs(1)))
+        },
+        sleep,
+    );
+    assert_eq!(
+        res,
+        Err(CheckpwnError::RateLimited(time::Duration::from_secs(1)))
+    );
+    assert_eq!(calls, 3);
+
+    // Other errors are not retried
+    for err in [
+        CheckpwnError::InvalidApiKey,
+        CheckpwnError::EmptyInput,
+        CheckpwnError::BadResponse,
+    ]
+    .iter()
+    {
+        let mut calls = 0;
+        let res: Result<(), CheckpwnError> = config.run_with_sleep(
+            || {
+                calls += 1;
+                Err(err.clone())
+            },
+            sleep,
+        );
+        assert_eq!(res.as_ref(), Err(err));
+        assert_eq!(calls, 1);
+    }
+}
+
+#[test]
+fn test_retry_backoff() {
+    let config = RetryConfig {
+        max_attempts: 4,
+        base_delay: time::Duration::from_secs(1),
+        max_delay: time::Duration::from_secs(3),
+        jitter: false,
+    };
+    let slept = std::cell::RefCell::new(Vec::new());
+
+    let mut calls = 0;
+    let res: Result<(), CheckpwnError> = config.run_with_sleep(
+        || {
+            calls += 1;
+            if calls % 2 == 0 {
+                Err(CheckpwnError::Timeout)
+            } else {
+                Err(CheckpwnError::Network)
+            }
+        },
+        |d| slept.borrow_mut().push(d),
+    );
+    assert_eq!(res, Err(CheckpwnError::Timeout));
+    assert_eq!(calls, 4);
+    // 1s, 2s, then 4s capped at 3s
+    assert_eq!(
+        *slept.borrow(),
+        vec![
+            time::Duration::from_secs(1),
+            time::Duration::from_secs(2),
+            time::Duration::from_secs(3)
+        ]
+    );
+
+    // Succeeds after a transient error
+    let mut calls = 0;
+    let res = config.run_with_sleep(
+        || {
+            calls += 1;
+            if calls == 1 {
+                Err(CheckpwnError::Network)
+            } else {
+                Ok(calls)
+            }
+        },
+        |_| {},
+    );
+    assert_eq!(res, Ok(2));
+}
+
+#[test]
+fn test_retry_backoff_jitter() {
+    let config = RetryConfig {
+        max_attempts: 10,
+        base_delay: time::Duration::from_secs(1),
+        max_delay: time::Duration::from_secs(30),
+        jitter: true,
+    };
+
+    for attempt in 1..10 {
+        let full = RetryConfig {
+            jitter: false,
+            ..config
+        }
+        .backoff(attempt);
+        let jittered = config.backoff(attempt);
+
+        assert!(jittered >= full / 2);
+        assert!(jittered <= full);
+    }
+
+    // Large attempt counts don't overflow
+    assert_eq!(
+        RetryConfig {
+            jitter: false,
+            ..config
+        }
+        .backoff(u8::MAX),
+        time::Duration::from_secs(30)
+    );
+}
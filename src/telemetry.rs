@@ -0,0 +1,92 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+use std::time::{Duration, Instant};
+
+/// Details about a single password check, recorded by `check_password_with_telemetry` or
+/// `CheckpwnClient::check_password_with_telemetry`.
+/// Can be used to export metrics about the requests sent to HIBP.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TelemetryCollector {
+    /// When the request to HIBP was started. `None` if no request was made.
+    pub request_start: Option<Instant>,
+    /// When the request to HIBP completed. `None` if no request was made.
+    pub request_end: Option<Instant>,
+    /// Status code of the response. `0` if no response was received.
+    pub http_status: u16,
+    /// Number of lines in the range response, including padding.
+    pub range_line_count: usize,
+    /// Whether the range was served from the `RangeCache` of the client. Always `false` for
+    /// a client without one, such as the default client used by `check_password_with_telemetry`.
+    pub cache_hit: bool,
+}
+
+impl TelemetryCollector {
+    /// Time between the start and end of the request, if both were recorded.
+    pub fn elapsed(&self) -> Option<Duration> {
+        match (self.request_start, self.request_end) {
+            (Some(start), Some(end)) => Some(end.saturating_duration_since(start)),
+            _ => None,
+        }
+    }
+
+    /// Labels describing the request, for use with e.g. Prometheus.
+    pub fn to_metric_labels(&self) -> Vec<(String, String)> {
+        vec![
+            ("http_status".to_string(), self.http_status.to_string()),
+            ("cache_hit".to_string(), self.cache_hit.to_string()),
+        ]
+    }
+}
+
+#[test]
+fn test_telemetry_default() {
+    let telemetry = TelemetryCollector::default();
+
+    assert!(telemetry.request_start.is_none());
+    assert!(telemetry.request_end.is_none());
+    assert_eq!(telemetry.http_status, 0);
+    assert_eq!(telemetry.range_line_count, 0);
+    assert!(!telemetry.cache_hit);
+    assert!(telemetry.elapsed().is_none());
+}
+
+#[test]
+fn test_telemetry_metric_labels() {
+    let start = Instant::now();
+    let telemetry = TelemetryCollector {
+        request_start: Some(start),
+        request_end: Some(start + Duration::from_millis(25)),
+        http_status: 200,
+        range_line_count: 800,
+        cache_hit: false,
+    };
+
+    assert_eq!(telemetry.elapsed(), Some(Duration::from_millis(25)));
+    assert_eq!(
+        telemetry.to_metric_labels(),
+        vec![
+            ("http_status".to_string(), "200".to_string()),
+            ("cache_hit".to_string(), "false".to_string()),
+        ]
+    );
+}
@@ -0,0 +1,516 @@
+// MIT License
+
+// Copyright (c) 2020-2022 brycx
+
+// Permission is hereby granted, free of charge, to any person obtaining a copy
+// of this software and associated documentation files (the "Software"), to deal
+// in the Software without restriction, including without limitation the rights
+// to use, copy, modify, merge, publish, distribute, sublicense, and/or sell
+// copies of the Software, and to permit persons to whom the Software is
+// furnished to do so, subject to the following conditions:
+
+// The above copyright notice and this permission notice shall be included in all
+// copies or substantial portions of the Software.
+
+// THE SOFTWARE IS PROVIDED "AS IS", WITHOUT WARRANTY OF ANY KIND, EXPRESS OR
+// IMPLIED, INCLUDING BUT NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY,
+// FITNESS FOR A PARTICULAR PURPOSE AND NONINFRINGEMENT. IN NO EVENT SHALL THE
+// AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM, DAMAGES OR OTHER
+// LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM,
+// OUT OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE
+// SOFTWARE.
+
+//! Test doubles for writing offline tests of code that uses this crate.
+//!
+//! # Example:
+//! ```rust
+//! use checkpwn_lib::testing::MockHttpClient;
+//! use checkpwn_lib::{CheckpwnClientBuilder, Password};
+//! use std::sync::Arc;
+//!
+//! let mock = Arc::new(MockHttpClient::new().with_response(
+//!     "https://api.pwnedpasswords.com/range/B1B37",
+//!     200,
+//!     "73A05C0ED0176787A4F1574FF0075F7521E:3752262",
+//! ));
+//! let client = CheckpwnClientBuilder::new().http_client(mock.clone()).build();
+//!
+//! assert!(client.check_password(&Password::new("qwerty")?)?);
+//! assert_eq!(mock.calls().len(), 1);
+//! # Ok::<(), checkpwn_lib::CheckpwnError>(())
+//! ```
+
+use crate::api;
+use crate::errors::CheckpwnError;
+use crate::http::HttpClient;
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// A request received by a `MockHttpClient`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MockCall {
+    /// URL the request was sent to.
+    pub url: String,
+    /// Headers set on the request.
+    pub headers: Vec<(String, String)>,
+}
+
+impl MockCall {
+    /// Value of the header `name`, if it was set. Header names are compared case-insensitively.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+type MockResponse = Result<(u16, String, Option<String>), CheckpwnError>;
+
+/// An `HttpClient` that records every request and returns pre-programmed responses,
+/// without sending anything over the network.
+///
+/// Responses are programmed per URL and returned in the order they were added.
+/// The last response for a URL is repeated once all others have been returned.
+/// Requests to a URL with no programmed responses return `Err(CheckpwnError::Network)`.
+#[derive(Debug, Default)]
+pub struct MockHttpClient {
+    responses: Mutex<HashMap<String, VecDeque<MockResponse>>>,
+    calls: Mutex<Vec<MockCall>>,
+}
+
+impl MockHttpClient {
+    /// Make a new `MockHttpClient` with no programmed responses.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Program a response with `status` and `body` for requests to `url`.
+    pub fn with_response(self, url: &str, status: u16, body: &str) -> Self {
+        self.push(url, Ok((status, body.to_string(), None)));
+        self
+    }
+
+    /// Program a response with `status`, `body` and an `ETag` header of `etag` for requests
+    /// to `url`.
+    pub fn with_etag_response(self, url: &str, status: u16, body: &str, etag: &str) -> Self {
+        self.push(url, Ok((status, body.to_string(), Some(etag.to_string()))));
+        self
+    }
+
+    /// Program `error` to be returned for requests to `url`.
+    pub fn with_error(self, url: &str, error: CheckpwnError) -> Self {
+        self.push(url, Err(error));
+        self
+    }
+
+    /// All requests received so far, in order.
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.calls.lock().unwrap().clone()
+    }
+
+    fn push(&self, url: &str, response: MockResponse) {
+        self.responses
+            .lock()
+            .unwrap()
+            .entry(url.to_string())
+            .or_default()
+            .push_back(response);
+    }
+}
+
+impl HttpClient for MockHttpClient {
+    fn get(&self, url: &str, headers: &[(&str, &str)]) -> Result<(u16, String), CheckpwnError> {
+        let (status, body, _) = self.get_with_etag(url, headers, None)?;
+        Ok((status, body))
+    }
+
+    fn get_with_etag(
+        &self,
+        url: &str,
+        headers: &[(&str, &str)],
+        _timeout: Option<std::time::Duration>,
+    ) -> Result<(u16, String, Option<String>), CheckpwnError> {
+        self.calls.lock().unwrap().push(MockCall {
+            url: url.to_string(),
+            headers: headers
+                .iter()
+                .map(|(name, value)| (name.to_string(), value.to_string()))
+                .collect(),
+        });
+
+        let mut responses = self.responses.lock().unwrap();
+        match responses.get_mut(url) {
+            Some(queue) if queue.len() > 1 => queue.pop_front().unwrap(),
+            Some(queue) => queue
+                .front()
+                .cloned()
+                .unwrap_or(Err(CheckpwnError::Network)),
+            None => Err(CheckpwnError::Network),
+        }
+    }
+}
+
+/// Offline stand-in for `check_password`, taking the SHA1 hash of the password as hex, for tests
+/// that must not depend on the network. Only knows the hashes of a few fixtures: "qwerty" and
+/// "password" are breached, and "dHRUKbDaKgIobOtX" is not. All other hashes return
+/// `Err(CheckpwnError::Network)`.
+///
+/// # Example:
+/// ```rust
+/// use checkpwn_lib::hash_password;
+/// use checkpwn_lib::testing::check_password_deterministic_test;
+///
+/// assert!(check_password_deterministic_test(&hash_password("qwerty"))?);
+/// assert!(!check_password_deterministic_test(&hash_password("dHRUKbDaKgIobOtX"))?);
+/// # Ok::<(), checkpwn_lib::CheckpwnError>(())
+/// ```
+pub fn check_password_deterministic_test(hash: &str) -> Result<bool, CheckpwnError> {
+    match hash.to_uppercase().as_str() {
+        // "qwerty"
+        "B1B3773A05C0ED0176787A4F1574FF0075F7521E" => Ok(true),
+        // "password"
+        "5BAA61E4C9B93F3F0682250B6CF8331B7EE68FD8" => Ok(true),
+        // "dHRUKbDaKgIobOtX"
+        "410AD7860C4EDCF2D2F65417336619F6CE16E8CE" => Ok(false),
+        _ => Err(CheckpwnError::Network),
+    }
+}
+
+type MockRoutes = Arc<Mutex<HashMap<String, (u16, String)>>>;
+
+/// A local HTTP server answering like HIBP, for offline tests that exercise the whole HTTP
+/// stack. Point a `CheckpwnClient` at it with `CheckpwnClientBuilder::hibp_base_url` and
+/// `CheckpwnClientBuilder::hibp_pass_base_url`.
+///
+/// Requests are routed by their path. The query string is ignored, except for `mode`, so that
+/// NTLM range requests are told apart from SHA1 ones. Requests to a path with no expected
+/// response get a 404. The server is stopped when the `MockHibpServer` is dropped.
+///
+/// # Example:
+/// ```rust
+/// use checkpwn_lib::testing::MockHibpServer;
+/// use checkpwn_lib::{CheckpwnClientBuilder, Password};
+///
+/// let mut server = MockHibpServer::start();
+/// server.expect_range("B1B37", "73A05C0ED0176787A4F1574FF0075F7521E:3752262");
+///
+/// let client = CheckpwnClientBuilder::new()
+///     .hibp_pass_base_url(&server.base_url())
+///     .build();
+///
+/// assert!(client.check_password(&Password::new("qwerty")?)?);
+/// # Ok::<(), checkpwn_lib::CheckpwnError>(())
+/// ```
+pub struct MockHibpServer {
+    server: Arc<tiny_http::Server>,
+    routes: MockRoutes,
+    handle: Option<thread::JoinHandle<()>>,
+}
+
+impl MockHibpServer {
+    /// Start a new server on a random local port.
+    ///
+    /// # Panics:
+    /// If the server can't be bound to a local port.
+    pub fn start() -> Self {
+        let server =
+            Arc::new(tiny_http::Server::http("127.0.0.1:0").expect("failed to start mock server"));
+        let routes = MockRoutes::default();
+
+        let handle = {
+            let server = server.clone();
+            let routes = routes.clone();
+            thread::spawn(move || {
+                for request in server.incoming_requests() {
+                    let (status, body) = routes
+                        .lock()
+                        .unwrap()
+                        .get(&route_key(request.url()))
+                        .cloned()
+                        .unwrap_or((404, String::new()));
+                    let _ = request
+                        .respond(tiny_http::Response::from_string(body).with_status_code(status));
+                }
+            })
+        };
+
+        Self {
+            server,
+            routes,
+            handle: Some(handle),
+        }
+    }
+
+    /// Respond to SHA1 range requests for the 5 character hash `prefix` with `body`.
+    pub fn expect_range(&mut self, prefix: &str, body: &str) -> &mut Self {
+        self.route(
+            format!("/range/{}", prefix.to_uppercase()),
+            200,
+            body.to_string(),
+        )
+    }
+
+    /// Respond to NTLM range requests, i.e. with `?mode=ntlm`, for the 5 character hash `prefix`
+    /// with `body`.
+    pub fn expect_range_ntlm(&mut self, prefix: &str, body: &str) -> &mut Self {
+        self.route(
+            format!("/range/{}?mode=ntlm", prefix.to_uppercase()),
+            200,
+            body.to_string(),
+        )
+    }
+
+    /// Respond to breached account requests for `account` with `status`, which is normally 200
+    /// for a breached account and 404 otherwise. If `breached`, the body lists a breach.
+    /// Paste requests for `account` get a 404.
+    pub fn expect_account_breach(
+        &mut self,
+        account: &str,
+        breached: bool,
+        status: u16,
+    ) -> &mut Self {
+        let account = api::encode_account(account);
+        let body = if breached {
+            r#"[{"Name":"MockBreach"}]"#.to_string()
+        } else {
+            String::new()
+        };
+
+        self.route(format!("/breachedaccount/{}", account), status, body)
+            .route(format!("/pasteaccount/{}", account), 404, String::new())
+    }
+
+    /// Respond to breached account requests for `account` with a 200 and `breaches`, a JSON
+    /// array of breaches as returned by HIBP, e.g. for `CheckpwnClient::get_breach_details`.
+    /// Paste requests for `account` get a 404.
+    pub fn expect_account_breaches(&mut self, account: &str, breaches: &str) -> &mut Self {
+        let account = api::encode_account(account);
+
+        self.route(
+            format!("/breachedaccount/{}", account),
+            200,
+            breaches.to_string(),
+        )
+        .route(format!("/pasteaccount/{}", account), 404, String::new())
+    }
+
+    /// The base URL of the server, e.g. `http://127.0.0.1:8080`.
+    pub fn base_url(&self) -> String {
+        format!(
+            "http://{}",
+            self.server
+                .server_addr()
+                .to_ip()
+                .expect("the server listens on an IP address")
+        )
+    }
+
+    fn route(&mut self, path: String, status: u16, body: String) -> &mut Self {
+        self.routes.lock().unwrap().insert(path, (status, body));
+        self
+    }
+}
+
+/// The key a request to `url` is routed by: its path, ignoring the query string, except for the
+/// `mode` parameter, which selects the hash algorithm of range requests.
+fn route_key(url: &str) -> String {
+    match url.split_once('?') {
+        Some((path, query)) => match query.split('&').find(|param| param.starts_with("mode=")) {
+            Some(mode) => format!("{}?{}", path, mode),
+            None => path.to_string(),
+        },
+        None => url.to_string(),
+    }
+}
+
+impl std::fmt::Debug for MockHibpServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockHibpServer")
+            .field("base_url", &self.base_url())
+            .finish()
+    }
+}
+
+impl Drop for MockHibpServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+#[test]
+fn test_mock_http_client() {
+    let mock = MockHttpClient::new()
+        .with_error("https://example.com/a", CheckpwnError::Network)
+        .with_response("https://example.com/a", 200, "first")
+        .with_response("https://example.com/a", 404, "");
+
+    assert_eq!(
+        mock.get("https://example.com/a", &[("User-Agent", "test")]),
+        Err(CheckpwnError::Network)
+    );
+    assert_eq!(
+        mock.get("https://example.com/a", &[]),
+        Ok((200, "first".to_string()))
+    );
+    assert_eq!(
+        mock.get("https://example.com/a", &[]),
+        Ok((404, String::new()))
+    );
+    // The last response is repeated
+    assert_eq!(
+        mock.get("https://example.com/a", &[]),
+        Ok((404, String::new()))
+    );
+    assert_eq!(
+        mock.get("https://example.com/b", &[]),
+        Err(CheckpwnError::Network)
+    );
+
+    let calls = mock.calls();
+    assert_eq!(calls.len(), 5);
+    assert_eq!(calls[0].url, "https://example.com/a");
+    assert_eq!(calls[0].header("user-agent"), Some("test"));
+    assert_eq!(calls[1].header("User-Agent"), None);
+    assert_eq!(calls[4].url, "https://example.com/b");
+}
+
+#[test]
+fn test_mock_http_client_etag() {
+    let mock = MockHttpClient::new()
+        .with_etag_response("https://example.com/a", 200, "first", "\"abc\"")
+        .with_response("https://example.com/a", 304, "");
+
+    assert_eq!(
+        mock.get_with_etag("https://example.com/a", &[], None),
+        Ok((200, "first".to_string(), Some("\"abc\"".to_string())))
+    );
+    assert_eq!(
+        mock.get_with_etag("https://example.com/a", &[], None),
+        Ok((304, String::new(), None))
+    );
+}
+
+#[test]
+fn test_mock_hibp_server() {
+    use crate::{CheckpwnClientBuilder, Password};
+
+    let mut server = MockHibpServer::start();
+    server
+        .expect_range("b1b37", "73A05C0ED0176787A4F1574FF0075F7521E:3752262")
+        .expect_account_breach("test+alias@example.com", true, 200)
+        .expect_account_breach("clean@example.com", false, 404)
+        .expect_account_breach("error@example.com", false, 500);
+
+    let client = CheckpwnClientBuilder::new()
+        .rate_limit_delay(std::time::Duration::from_millis(0))
+        .hibp_base_url(&server.base_url())
+        .hibp_pass_base_url(&server.base_url())
+        .build();
+
+    assert_eq!(
+        client.check_password(&Password::new("qwerty").unwrap()),
+        Ok(true)
+    );
+    // No expected range, so a 404
+    assert_eq!(
+        client.check_password(&Password::new("password").unwrap()),
+        Ok(false)
+    );
+    assert_eq!(
+        client.check_account("test+alias@example.com", "key"),
+        Ok(true)
+    );
+    assert_eq!(client.check_account("clean@example.com", "key"), Ok(false));
+    assert_eq!(
+        client.check_account("error@example.com", "key"),
+        Err(CheckpwnError::ServiceUnavailable)
+    );
+}
+
+#[test]
+fn test_mock_hibp_server_query_strings() {
+    use crate::{CheckpwnClientBuilder, HashAlgorithm, Password};
+
+    assert_eq!(route_key("/range/B1B37"), "/range/B1B37");
+    assert_eq!(
+        route_key("/range/8846F?mode=ntlm"),
+        "/range/8846F?mode=ntlm"
+    );
+    assert_eq!(
+        route_key("/breachedaccount/a?truncateResponse=false&includeUnverified=false"),
+        "/breachedaccount/a"
+    );
+
+    let mut server = MockHibpServer::start();
+    server
+        .expect_range("8846F", "0000000000000000000000000000000000A:1")
+        .expect_range_ntlm("8846F", "7EAEE8FB117AD06BDD830B7586C:3");
+
+    let client = CheckpwnClientBuilder::new()
+        .rate_limit_delay(std::time::Duration::from_millis(0))
+        .hibp_base_url(&server.base_url())
+        .hibp_pass_base_url(&server.base_url())
+        .build();
+
+    // The NTLM hash of "password" starts with 8846F, and is only in the NTLM range
+    let ntlm = Password::new_with_algorithm("password", HashAlgorithm::Ntlm).unwrap();
+    assert_eq!(client.check_password(&ntlm), Ok(true));
+    // The SHA1 hash starts with 5BAA6, which has no expected range
+    assert_eq!(
+        client.check_password(&Password::new("password").unwrap()),
+        Ok(false)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_mock_hibp_server_breach_details() {
+    use crate::CheckpwnClientBuilder;
+
+    let mut server = MockHibpServer::start();
+    server.expect_account_breaches(
+        "test@example.com",
+        &format!("[{}]", crate::breach::BREACH_FIXTURE),
+    );
+
+    for include_unverified in [true, false].iter() {
+        let client = CheckpwnClientBuilder::new()
+            .rate_limit_delay(std::time::Duration::from_millis(0))
+            .hibp_base_url(&server.base_url())
+            .include_unverified(*include_unverified)
+            .build();
+
+        let breaches = client
+            .get_breach_details("test@example.com", "key")
+            .unwrap();
+        assert_eq!(breaches.len(), 1);
+        assert_eq!(breaches[0].name, "Adobe");
+        assert_eq!(
+            client.check_account_breached_since("test@example.com", "key", "2013-01-01"),
+            Ok(true)
+        );
+        assert_eq!(client.check_account("test@example.com", "key"), Ok(true));
+        assert!(client
+            .get_breach_details("clean@example.com", "key")
+            .unwrap()
+            .is_empty());
+    }
+}
+
+#[test]
+fn test_check_password_deterministic() {
+    use crate::hash_password;
+
+    assert!(check_password_deterministic_test(&hash_password("qwerty")).unwrap());
+    assert!(check_password_deterministic_test(&hash_password("password")).unwrap());
+    assert!(!check_password_deterministic_test(&hash_password("dHRUKbDaKgIobOtX")).unwrap());
+    assert_eq!(
+        check_password_deterministic_test(&hash_password("unknown fixture")).unwrap_err(),
+        CheckpwnError::Network
+    );
+}